@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use solana_instructions_sysvar::get_instruction_relative;
+use solana_sha256_hasher::hashv;
 
 
 declare_id!("F8ScaDMtYwunu5Xx1geVDPoVon5C4PyjaTsoFbAdCkhu");
@@ -12,7 +17,40 @@ const MAX_SOURCE_LEN: usize = 16;
 const MAX_PROPOSAL_ID_LEN: usize = 128;
 const MAX_GOV_PROPOSAL_TEXT_LEN: usize = 512;
 const MAX_REVISION_TEXT_LEN: usize = 512;
-const ACTION_LAMPORTS: u64 = 1_000_000;
+const DEFAULT_MAX_ACTION_LAMPORTS: u64 = 1_000_000;
+const MAX_PROPOSAL_ACTIONS: usize = 4;
+const MAX_PROPOSAL_INSTRUCTIONS: u8 = 4;
+const MAX_INSTRUCTION_ACCOUNTS: usize = 8;
+const MAX_INSTRUCTION_DATA_LEN: usize = 256;
+const REQUEST_TTL_SECS: i64 = 86_400;
+const DEFAULT_DISPUTE_RESOLUTION_SECS: i64 = 7 * 86_400;
+
+const PAUSE_ORACLE: u8 = 1 << 0;
+const PAUSE_GOVERNANCE: u8 = 1 << 1;
+const PAUSE_TREASURY: u8 = 1 << 2;
+
+const MAX_JUDGE_PANEL_SIZE: u8 = 32;
+const CONSENSUS_MAJORITY: u8 = 1;
+const CONSENSUS_SUPERMAJORITY: u8 = 2;
+const CONSENSUS_UNANIMITY: u8 = 3;
+
+const POLICY_JUDGES_ONLY: u8 = 1;
+const POLICY_VOTES_ONLY: u8 = 2;
+const POLICY_BOTH_APPROVE: u8 = 3;
+const POLICY_WEIGHTED: u8 = 4;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+const MAX_DELEGATORS_PER_VOTE: usize = 8;
+
+const MAX_ATTESTORS: usize = 8;
+const MAX_RECEIPT_PROOF_DEPTH: usize = 32;
+const RECEIPT_LEAF_PREFIX: u8 = 0;
+const RECEIPT_NODE_PREFIX: u8 = 1;
+const MAX_RELAYER_PANEL: u8 = 8;
+const MAX_CALLBACK_ACCOUNTS: usize = 4;
+pub const CALLBACK_AUTHORITY_SEED: &[u8] = b"callback";
+pub const CALLBACK_IX_NAME: &str = "ambient_callback";
+const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
 
 #[program]
 pub mod ambient_svm_hello {
@@ -22,29 +60,154 @@ pub mod ambient_svm_hello {
         let cfg = &mut ctx.accounts.config;
         cfg.admin = ctx.accounts.admin.key();
         cfg.relayer = relayer;
+        cfg.pending_admin = Pubkey::default();
+        cfg.paused = false;
+        cfg.pause_flags = 0;
+        cfg.attestors = Vec::new();
+        cfg.min_request_fee = 0;
+        cfg.challenge_period_secs = 0;
+        cfg.dispute_bond_lamports = 0;
+        cfg.dispute_resolution_secs = DEFAULT_DISPUTE_RESOLUTION_SECS;
+        Ok(())
+    }
+
+    pub fn set_min_request_fee(ctx: Context<UpdateConfig>, min_request_fee: u64) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        cfg.min_request_fee = min_request_fee;
+        Ok(())
+    }
+
+    pub fn set_pause(ctx: Context<UpdateConfig>, paused: bool, pause_flags: u8) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        cfg.paused = paused;
+        cfg.pause_flags = pause_flags;
+        Ok(())
+    }
+
+    pub fn add_relayer(ctx: Context<AddRelayer>, relayer: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
+        );
+        let entry = &mut ctx.accounts.relayer_entry;
+        entry.relayer = relayer;
+        entry.bump = ctx.bumps.relayer_entry;
+        Ok(())
+    }
+
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
+        );
+        Ok(())
+    }
+
+    pub fn update_relayer(ctx: Context<UpdateConfig>, relayer: Pubkey) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        cfg.relayer = relayer;
+        Ok(())
+    }
+
+    pub fn set_dispute_params(
+        ctx: Context<UpdateConfig>,
+        challenge_period_secs: i64,
+        dispute_bond_lamports: u64,
+        dispute_resolution_secs: i64,
+    ) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        require!(
+            challenge_period_secs >= 0 && dispute_resolution_secs > 0,
+            ErrorCode::BadDisputeParams
+        );
+        cfg.challenge_period_secs = challenge_period_secs;
+        cfg.dispute_bond_lamports = dispute_bond_lamports;
+        cfg.dispute_resolution_secs = dispute_resolution_secs;
+        Ok(())
+    }
+
+    pub fn add_attestor(ctx: Context<UpdateConfig>, attestor: Pubkey) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        require!(!cfg.attestors.contains(&attestor), ErrorCode::AttestorExists);
+        require!(cfg.attestors.len() < MAX_ATTESTORS, ErrorCode::TooManyAttestors);
+        cfg.attestors.push(attestor);
+        Ok(())
+    }
+
+    pub fn remove_attestor(ctx: Context<UpdateConfig>, attestor: Pubkey) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        let index = cfg
+            .attestors
+            .iter()
+            .position(|a| *a == attestor)
+            .ok_or(ErrorCode::UnknownAttestor)?;
+        cfg.attestors.remove(index);
+        Ok(())
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require_keys_eq!(cfg.admin, ctx.accounts.admin.key(), ErrorCode::NotAdmin);
+        cfg.pending_admin = new_admin;
         Ok(())
     }
 
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require!(cfg.pending_admin != Pubkey::default(), ErrorCode::NoPendingAdmin);
+        require_keys_eq!(
+            cfg.pending_admin,
+            ctx.accounts.new_admin.key(),
+            ErrorCode::NotPendingAdmin
+        );
+        cfg.admin = cfg.pending_admin;
+        cfg.pending_admin = Pubkey::default();
+        Ok(())
+    }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_judge_request(
         ctx: Context<CreateJudgeRequest>,
         criteria: String,
         input_a: String,
         input_b: String,
         nonce: u64,
+        fee_lamports: u64,
+        quorum: RelayerQuorumParams,
+        callback: Option<CallbackParams>,
     ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
         require!(
-            criteria.as_bytes().len() <= MAX_CRITERIA_LEN,
+            fee_lamports >= ctx.accounts.config.min_request_fee,
+            ErrorCode::FeeTooLow
+        );
+        require!(
+            criteria.len() <= MAX_CRITERIA_LEN,
             ErrorCode::CriteriaTooLong
         );
         require!(
-            input_a.as_bytes().len() <= MAX_INPUT_LEN,
+            input_a.len() <= MAX_INPUT_LEN,
             ErrorCode::InputTooLong
         );
         require!(
-            input_b.as_bytes().len() <= MAX_INPUT_LEN,
+            input_b.len() <= MAX_INPUT_LEN,
             ErrorCode::InputTooLong
         );
+        quorum.validate()?;
+        if let Some(callback) = &callback {
+            require!(
+                callback.accounts.len() <= MAX_CALLBACK_ACCOUNTS,
+                ErrorCode::TooManyCallbackAccounts
+            );
+        }
 
         let req = &mut ctx.accounts.request;
         req.authority = ctx.accounts.user.key();
@@ -59,6 +222,55 @@ pub mod ambient_svm_hello {
 
         req.response_hash = [0u8; 32];
         req.receipt_root = [0u8; 32];
+        req.relayer = Pubkey::default();
+        req.prompt_hash = [0u8; 32];
+        req.model_id = String::new();
+
+        let now = Clock::get()?.unix_timestamp;
+        req.created_at = now;
+        req.expires_at = now.checked_add(REQUEST_TTL_SECS).unwrap();
+        req.fee_lamports = fee_lamports;
+
+        if quorum.relayer_quorum <= 1 {
+            req.relayer_quorum = 1;
+            req.relayer_panel = 1;
+        } else {
+            req.relayer_quorum = quorum.relayer_quorum;
+            req.relayer_panel = quorum.relayer_panel;
+        }
+        req.match_response_hash = quorum.match_response_hash;
+        req.submissions = 0;
+        req.tallies = Vec::new();
+        match callback {
+            Some(callback) => {
+                req.callback_program = callback.program_id;
+                req.callback_accounts = callback.accounts;
+            }
+            None => {
+                req.callback_program = Pubkey::default();
+                req.callback_accounts = Vec::new();
+            }
+        }
+        req.callback_delivered = false;
+
+        emit!(JudgeRequestCreated {
+            request: req.key(),
+            authority: req.authority,
+            nonce,
+            criteria_hash: hashv(&[req.criteria.as_bytes()]).to_bytes(),
+            fee_lamports,
+            relayer_quorum: req.relayer_quorum,
+            relayer_panel: req.relayer_panel,
+            callback_program: req.callback_program,
+            expires_at: req.expires_at,
+        });
+
+        escrow_request_fee(
+            &ctx.accounts.user,
+            &ctx.accounts.request.to_account_info(),
+            &ctx.accounts.system_program,
+            fee_lamports,
+        )?;
 
         Ok(())
     }
@@ -68,42 +280,205 @@ pub mod ambient_svm_hello {
         decision: u8,
         response_hash: [u8; 32],
         receipt_root: [u8; 32],
+        prompt_hash: [u8; 32],
+        model_id: String,
     ) -> Result<()> {
-        require_keys_eq!(
-            ctx.accounts.config.relayer,
-            ctx.accounts.relayer.key(),
-            ErrorCode::BadRelayer
-        );
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        check_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer_entry,
+            &ctx.accounts.relayer.key(),
+        )?;
 
         let req = &mut ctx.accounts.request;
         require!(req.status == 0, ErrorCode::AlreadyFulfilled);
-        require!(decision >= 1 && decision <= 3, ErrorCode::BadDecision);
+        require!(req.relayer_quorum <= 1, ErrorCode::RelayerQuorumRequired);
+        require!(
+            Clock::get()?.unix_timestamp < req.expires_at,
+            ErrorCode::RequestExpired
+        );
+        require!((1..=3).contains(&decision), ErrorCode::BadDecision);
+        require!(
+            model_id.len() <= MAX_MODEL_ID_LEN,
+            ErrorCode::ModelIdTooLong
+        );
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.instructions,
+            &attestation_message(
+                &req.key(),
+                decision,
+                &response_hash,
+                &receipt_root,
+                &prompt_hash,
+                &model_id,
+            ),
+        )?;
 
         req.decision = decision;
+        req.relayer = ctx.accounts.relayer.key();
         req.response_hash = response_hash;
         req.receipt_root = receipt_root;
+        req.prompt_hash = prompt_hash;
+        req.model_id = model_id;
         req.status = 1;
 
+        emit!(JudgeRequestFulfilled {
+            request: req.key(),
+            relayer: req.relayer,
+            decision,
+            response_hash,
+            receipt_root,
+            prompt_hash,
+            model_id: req.model_id.clone(),
+        });
+
+        let fee = req.fee_lamports;
+        pay_request_fee(
+            &ctx.accounts.request.to_account_info(),
+            &ctx.accounts.relayer.to_account_info(),
+            fee,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn submit_relayer_result(
+        ctx: Context<SubmitRelayerResult>,
+        decision: u8,
+        response_hash: [u8; 32],
+        receipt_root: [u8; 32],
+        prompt_hash: [u8; 32],
+        model_id: String,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        let relayer = ctx.accounts.relayer.key();
+        check_relayer(&ctx.accounts.config, &ctx.accounts.relayer_entry, &relayer)?;
+
+        let req = &mut ctx.accounts.request;
+        require!(req.status == 0, ErrorCode::AlreadyFulfilled);
+        require!(req.relayer_quorum > 1, ErrorCode::RelayerQuorumNotEnabled);
+        require!(
+            Clock::get()?.unix_timestamp < req.expires_at,
+            ErrorCode::RequestExpired
+        );
+        require!((1..=3).contains(&decision), ErrorCode::BadDecision);
+        require!(
+            model_id.len() <= MAX_MODEL_ID_LEN,
+            ErrorCode::ModelIdTooLong
+        );
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.instructions,
+            &attestation_message(
+                &req.key(),
+                decision,
+                &response_hash,
+                &receipt_root,
+                &prompt_hash,
+                &model_id,
+            ),
+        )?;
+
+        let result = &mut ctx.accounts.relayer_result;
+        result.request = req.key();
+        result.relayer = relayer;
+        result.decision = decision;
+        result.response_hash = response_hash;
+        result.receipt_root = receipt_root;
+        result.prompt_hash = prompt_hash;
+        result.model_id = model_id.clone();
+        result.bump = ctx.bumps.relayer_result;
+
+        let agreeing = req.tally(decision, response_hash);
+        req.submissions += 1;
+        if agreeing >= req.relayer_quorum {
+            req.decision = decision;
+            req.relayer = relayer;
+            req.response_hash = response_hash;
+            req.receipt_root = receipt_root;
+            req.prompt_hash = prompt_hash;
+            req.model_id = model_id;
+            req.status = 1;
+        } else if req.submissions >= req.relayer_panel {
+            req.status = 2;
+        }
+
+        emit!(RelayerResultSubmitted {
+            request: req.key(),
+            relayer,
+            decision,
+            response_hash,
+            receipt_root,
+            agreeing,
+            status: req.status,
+        });
+        if req.status == 1 {
+            emit!(JudgeRequestFulfilled {
+                request: req.key(),
+                relayer,
+                decision,
+                response_hash,
+                receipt_root,
+                prompt_hash,
+                model_id: req.model_id.clone(),
+            });
+        }
+
+        let share = req.fee_lamports / req.relayer_panel as u64;
+        pay_request_fee(
+            &ctx.accounts.request.to_account_info(),
+            &ctx.accounts.relayer.to_account_info(),
+            share,
+        )?;
+
         Ok(())
     }
 
+    pub fn deliver_callback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeliverCallback<'info>>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        let req = &mut ctx.accounts.request;
+        require!(req.status == 1, ErrorCode::NotFulfilled);
+        require!(
+            req.callback_program != Pubkey::default(),
+            ErrorCode::NoCallback
+        );
+        require!(!req.callback_delivered, ErrorCode::CallbackDelivered);
+        req.callback_delivered = true;
+
+        emit!(CallbackDelivered {
+            request: req.key(),
+            callback_program: req.callback_program,
+        });
+
+        invoke_judge_callback(&ctx.accounts.request, ctx.remaining_accounts, ctx.program_id)
+    }
+
     pub fn create_proposal_request(
         ctx: Context<CreateProposalRequest>,
         source: String,
         proposal_id: String,
         proposal_text: String,
         nonce: u64,
+        fee_lamports: u64,
     ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        require!(
+            fee_lamports >= ctx.accounts.config.min_request_fee,
+            ErrorCode::FeeTooLow
+        );
         require!(
-            source.as_bytes().len() <= MAX_SOURCE_LEN,
+            source.len() <= MAX_SOURCE_LEN,
             ErrorCode::SourceTooLong
         );
         require!(
-            proposal_id.as_bytes().len() <= MAX_PROPOSAL_ID_LEN,
+            proposal_id.len() <= MAX_PROPOSAL_ID_LEN,
             ErrorCode::ProposalIdTooLong
         );
         require!(
-            proposal_text.as_bytes().len() <= MAX_PROPOSAL_TEXT_LEN,
+            proposal_text.len() <= MAX_PROPOSAL_TEXT_LEN,
             ErrorCode::ProposalTooLong
         );
 
@@ -122,6 +497,31 @@ pub mod ambient_svm_hello {
         req.receipt_root = [0u8; 32];
         req.prompt_hash = [0u8; 32];
         req.model_id = String::new();
+        req.relayer = Pubkey::default();
+
+        let now = Clock::get()?.unix_timestamp;
+        req.created_at = now;
+        req.expires_at = now.checked_add(REQUEST_TTL_SECS).unwrap();
+        req.fee_lamports = fee_lamports;
+        req.challenge_ends_at = 0;
+
+        emit!(ProposalRequestCreated {
+            request: req.key(),
+            authority: req.authority,
+            nonce,
+            source: req.source.clone(),
+            proposal_id: req.proposal_id.clone(),
+            text_hash: hashv(&[req.proposal_text.as_bytes()]).to_bytes(),
+            fee_lamports,
+            expires_at: req.expires_at,
+        });
+
+        escrow_request_fee(
+            &ctx.accounts.user,
+            &ctx.accounts.request.to_account_info(),
+            &ctx.accounts.system_program,
+            fee_lamports,
+        )?;
 
         Ok(())
     }
@@ -134,33 +534,300 @@ pub mod ambient_svm_hello {
         prompt_hash: [u8; 32],
         model_id: String,
     ) -> Result<()> {
-        require_keys_eq!(
-            ctx.accounts.config.relayer,
-            ctx.accounts.relayer.key(),
-            ErrorCode::BadRelayer
-        );
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        check_relayer(
+            &ctx.accounts.config,
+            &ctx.accounts.relayer_entry,
+            &ctx.accounts.relayer.key(),
+        )?;
 
         let req = &mut ctx.accounts.request;
         require!(req.status == 0, ErrorCode::AlreadyFulfilled);
-        require!(verdict_code >= 1 && verdict_code <= 3, ErrorCode::BadVerdict);
         require!(
-            model_id.as_bytes().len() <= MAX_MODEL_ID_LEN,
+            Clock::get()?.unix_timestamp < req.expires_at,
+            ErrorCode::RequestExpired
+        );
+        require!((1..=3).contains(&verdict_code), ErrorCode::BadVerdict);
+        require!(
+            model_id.len() <= MAX_MODEL_ID_LEN,
             ErrorCode::ModelIdTooLong
         );
+        verify_attestation(
+            &ctx.accounts.config,
+            &ctx.accounts.instructions,
+            &attestation_message(
+                &req.key(),
+                verdict_code,
+                &summary_hash,
+                &receipt_root,
+                &prompt_hash,
+                &model_id,
+            ),
+        )?;
 
         req.verdict_code = verdict_code;
         req.summary_hash = summary_hash;
         req.receipt_root = receipt_root;
         req.prompt_hash = prompt_hash;
         req.model_id = model_id;
+        req.relayer = ctx.accounts.relayer.key();
         req.status = 1;
+        req.challenge_ends_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(ctx.accounts.config.challenge_period_secs)
+            .unwrap();
+
+        emit!(ProposalRequestFulfilled {
+            request: req.key(),
+            relayer: req.relayer,
+            verdict_code,
+            summary_hash,
+            receipt_root,
+            prompt_hash,
+            model_id: req.model_id.clone(),
+            challenge_ends_at: req.challenge_ends_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn dispute_proposal_request(ctx: Context<DisputeProposalRequest>) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        let req = &mut ctx.accounts.request;
+        require!(req.status == 1, ErrorCode::NotProposed);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < req.challenge_ends_at, ErrorCode::ChallengeClosed);
+        req.status = 2;
+
+        let bond = ctx.accounts.config.dispute_bond_lamports;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.request = req.key();
+        dispute.disputer = ctx.accounts.disputer.key();
+        dispute.bond_lamports = bond;
+        dispute.bump = ctx.bumps.dispute;
+        dispute.resolve_ends_at = now
+            .checked_add(ctx.accounts.config.dispute_resolution_secs)
+            .unwrap();
+
+        emit!(ProposalRequestDisputed {
+            request: req.key(),
+            disputer: dispute.disputer,
+            bond_lamports: bond,
+            resolve_ends_at: dispute.resolve_ends_at,
+        });
+
+        if bond > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.disputer.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            );
+            system_program::transfer(cpi_ctx, bond)?;
+        }
 
         Ok(())
     }
 
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        verdict_code: u8,
+        summary_hash: [u8; 32],
+        receipt_root: [u8; 32],
+        prompt_hash: [u8; 32],
+        model_id: String,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        require!((1..=3).contains(&verdict_code), ErrorCode::BadVerdict);
+        require!(
+            model_id.len() <= MAX_MODEL_ID_LEN,
+            ErrorCode::ModelIdTooLong
+        );
+        let resolver = ctx.accounts.resolver.key();
+        require_keys_neq!(
+            resolver,
+            ctx.accounts.dispute.disputer,
+            ErrorCode::NotDisputeResolver
+        );
+        let req = &mut ctx.accounts.request;
+        require!(req.status == 2, ErrorCode::NotDisputed);
+        require!(
+            Clock::get()?.unix_timestamp < ctx.accounts.dispute.resolve_ends_at,
+            ErrorCode::DisputeResolutionClosed
+        );
+
+        let is_admin = resolver == ctx.accounts.config.admin;
+        let is_other_relayer = resolver != req.relayer
+            && check_relayer(&ctx.accounts.config, &ctx.accounts.relayer_entry, &resolver)
+                .is_ok();
+        require!(is_admin || is_other_relayer, ErrorCode::NotDisputeResolver);
+        if !is_admin {
+            verify_attestation(
+                &ctx.accounts.config,
+                &ctx.accounts.instructions,
+                &attestation_message(
+                    &req.key(),
+                    verdict_code,
+                    &summary_hash,
+                    &receipt_root,
+                    &prompt_hash,
+                    &model_id,
+                ),
+            )?;
+        }
+
+        let upheld = verdict_code == req.verdict_code
+            && summary_hash == req.summary_hash
+            && receipt_root == req.receipt_root
+            && prompt_hash == req.prompt_hash
+            && model_id == req.model_id;
+        if !upheld {
+            req.verdict_code = verdict_code;
+            req.summary_hash = summary_hash;
+            req.receipt_root = receipt_root;
+            req.prompt_hash = prompt_hash;
+            req.model_id = model_id;
+        }
+        req.status = 3;
+
+        emit!(DisputeResolved {
+            request: req.key(),
+            resolver,
+            upheld,
+            verdict_code: req.verdict_code,
+            summary_hash: req.summary_hash,
+            receipt_root: req.receipt_root,
+        });
+
+        let fee = req.fee_lamports;
+        let request_info = ctx.accounts.request.to_account_info();
+        let dispute_info = ctx.accounts.dispute.to_account_info();
+        if upheld {
+            pay_request_fee(&request_info, &ctx.accounts.relayer.to_account_info(), fee)?;
+            let bond = ctx.accounts.dispute.bond_lamports;
+            pay_request_fee(&dispute_info, &ctx.accounts.relayer.to_account_info(), bond)?;
+        } else {
+            pay_request_fee(&request_info, &ctx.accounts.authority.to_account_info(), fee)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        let req = &ctx.accounts.request;
+        require!(req.status == 2, ErrorCode::NotDisputed);
+        let dispute = &ctx.accounts.dispute;
+        require!(
+            Clock::get()?.unix_timestamp >= dispute.resolve_ends_at,
+            ErrorCode::DisputeResolutionOpen
+        );
+        emit!(DisputeExpired {
+            request: req.key(),
+            authority: req.authority,
+            disputer: dispute.disputer,
+            fee_lamports: req.fee_lamports,
+            bond_lamports: dispute.bond_lamports,
+        });
+        Ok(())
+    }
+
+    pub fn finalize_proposal_request(ctx: Context<FinalizeProposalRequest>) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_ORACLE)?;
+        let req = &mut ctx.accounts.request;
+        require!(req.status == 1, ErrorCode::NotProposed);
+        require!(
+            Clock::get()?.unix_timestamp >= req.challenge_ends_at,
+            ErrorCode::ChallengeOpen
+        );
+        req.status = 3;
+
+        emit!(ProposalRequestFinalized {
+            request: req.key(),
+            relayer: req.relayer,
+            verdict_code: req.verdict_code,
+            summary_hash: req.summary_hash,
+            receipt_root: req.receipt_root,
+        });
+
+        let fee = req.fee_lamports;
+        pay_request_fee(
+            &ctx.accounts.request.to_account_info(),
+            &ctx.accounts.relayer.to_account_info(),
+            fee,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_judge_request(ctx: Context<CancelJudgeRequest>) -> Result<()> {
+        let req = &ctx.accounts.request;
+        require_keys_eq!(
+            req.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRequestAuthority
+        );
+        require!(req.status == 0 || req.status == 2, ErrorCode::AlreadyFulfilled);
+        emit!(RequestCancelled {
+            request: req.key(),
+            authority: req.authority,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_proposal_request(ctx: Context<CancelProposalRequest>) -> Result<()> {
+        let req = &ctx.accounts.request;
+        require_keys_eq!(
+            req.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRequestAuthority
+        );
+        require!(req.status == 0, ErrorCode::AlreadyFulfilled);
+        emit!(RequestCancelled {
+            request: req.key(),
+            authority: req.authority,
+        });
+        Ok(())
+    }
+
+    pub fn verify_receipt_leaf(
+        ctx: Context<VerifyReceiptLeaf>,
+        leaf: [u8; 32],
+        leaf_index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(proof.len() <= MAX_RECEIPT_PROOF_DEPTH, ErrorCode::ReceiptProofTooLong);
+        require!(
+            u64::from(leaf_index) < 1u64 << proof.len(),
+            ErrorCode::ReceiptLeafIndexOutOfRange
+        );
+        let receipt_root = stored_receipt_root(&ctx.accounts.receipt_account)?;
+        require!(receipt_root != [0u8; 32], ErrorCode::NoReceiptRoot);
+        require!(
+            verify_receipt_proof(&receipt_root, &leaf, leaf_index, &proof),
+            ErrorCode::ReceiptProofMismatch
+        );
+        Ok(())
+    }
+
     pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
         let treasury = &mut ctx.accounts.treasury;
         treasury.bump = ctx.bumps.treasury;
+        treasury.max_action_lamports = DEFAULT_MAX_ACTION_LAMPORTS;
+        Ok(())
+    }
+
+    pub fn set_max_action_lamports(
+        ctx: Context<SetMaxActionLamports>,
+        max_action_lamports: u64,
+    ) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
+        );
+        ctx.accounts.treasury.max_action_lamports = max_action_lamports;
         Ok(())
     }
 
@@ -195,97 +862,527 @@ pub mod ambient_svm_hello {
             },
         );
         system_program::transfer(cpi_ctx, amount)?;
+        emit!(TreasuryFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            vault_balance: ctx.accounts.treasury_vault.lamports(),
+        });
         Ok(())
     }
 
-    pub fn create_governance_proposal(
-        ctx: Context<CreateGovernanceProposal>,
-        proposal_text: String,
-        revision_number: u64,
-        nonce: u64,
+    pub fn init_judge_registry(ctx: Context<InitJudgeRegistry>, authority: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
+        );
+        let registry = &mut ctx.accounts.judge_registry;
+        registry.authority = authority;
+        registry.bump = ctx.bumps.judge_registry;
+        Ok(())
+    }
+
+    pub fn set_judge_registry_authority(
+        ctx: Context<UpdateJudgeRegistry>,
+        authority: Pubkey,
     ) -> Result<()> {
-        require!(
-            proposal_text.as_bytes().len() <= MAX_GOV_PROPOSAL_TEXT_LEN,
-            ErrorCode::GovernanceTextTooLong
+        let registry = &mut ctx.accounts.judge_registry;
+        require_keys_eq!(
+            registry.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRegistryAuthority
         );
-        require!(revision_number == 0, ErrorCode::BadRevisionNumber);
+        registry.authority = authority;
+        Ok(())
+    }
 
-        let proposal = &mut ctx.accounts.proposal;
-        proposal.authority = ctx.accounts.user.key();
-        proposal.status = 0;
-        proposal.nonce = nonce;
-        proposal.revision_count = 1;
-        proposal.votes_for = 0;
-        proposal.votes_against = 0;
-        proposal.votes_abstain = 0;
-        proposal.judge_approve = 0;
-        proposal.judge_reject = 0;
-        proposal.judge_needs = 0;
-        proposal.final_verdict = 0;
-        proposal.proposal_text = proposal_text.clone();
+    pub fn add_judge(ctx: Context<AddJudge>, judge: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.judge_registry.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRegistryAuthority
+        );
+        let entry = &mut ctx.accounts.judge_entry;
+        entry.judge = judge;
+        entry.active = true;
+        entry.bump = ctx.bumps.judge_entry;
+        Ok(())
+    }
 
-        let revision = &mut ctx.accounts.revision;
-        revision.proposal = proposal.key();
-        revision.revision_number = 0;
-        revision.text = proposal_text;
+    pub fn set_judge_active(ctx: Context<UpdateJudge>, active: bool) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.judge_registry.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRegistryAuthority
+        );
+        ctx.accounts.judge_entry.active = active;
+        Ok(())
+    }
 
+    pub fn remove_judge(ctx: Context<RemoveJudge>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.judge_registry.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRegistryAuthority
+        );
         Ok(())
     }
 
-    pub fn add_revision(
-        ctx: Context<AddRevision>,
-        revision_number: u64,
-        revision_text: String,
+    pub fn init_governance_config(
+        ctx: Context<InitGovernanceConfig>,
+        params: GovernanceParams,
     ) -> Result<()> {
-        require!(
-            revision_text.as_bytes().len() <= MAX_REVISION_TEXT_LEN,
-            ErrorCode::GovernanceTextTooLong
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
         );
-        let proposal = &mut ctx.accounts.proposal;
-        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
-        require!(revision_number == proposal.revision_count, ErrorCode::BadRevisionNumber);
-        require_keys_eq!(proposal.authority, ctx.accounts.user.key(), ErrorCode::NotAuthority);
-
-        proposal.revision_count = proposal.revision_count.checked_add(1).unwrap();
-        proposal.proposal_text = revision_text.clone();
-
-        let revision = &mut ctx.accounts.revision;
-        revision.proposal = proposal.key();
-        revision.revision_number = revision_number;
-        revision.text = revision_text;
-
+        params.validate()?;
+        let gov = &mut ctx.accounts.governance_config;
+        gov.bump = ctx.bumps.governance_config;
+        gov.params = params;
         Ok(())
     }
 
-    pub fn cast_vote(ctx: Context<CastVote>, choice: u8) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+    pub fn update_governance_config(
+        ctx: Context<UpdateGovernanceConfig>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.config.admin,
+            ctx.accounts.admin.key(),
+            ErrorCode::NotAdmin
+        );
+        params.validate()?;
+        ctx.accounts.governance_config.params = params;
+        Ok(())
+    }
+
+    pub fn deposit_governing_tokens(
+        ctx: Context<DepositGoverningTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::BadAmount);
+
+        let deposit = &mut ctx.accounts.voter_deposit;
+        if deposit.owner == Pubkey::default() {
+            deposit.owner = ctx.accounts.owner.key();
+            deposit.mint = ctx.accounts.governing_mint.key();
+            deposit.amount = 0;
+            deposit.locked_until = 0;
+            deposit.bump = ctx.bumps.voter_deposit;
+        }
+        deposit.amount = deposit.amount.checked_add(amount).unwrap();
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                to: ctx.accounts.deposit_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn withdraw_governing_tokens(
+        ctx: Context<WithdrawGoverningTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        let deposit = &mut ctx.accounts.voter_deposit;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= deposit.locked_until, ErrorCode::DepositLocked);
+        require!(amount <= deposit.amount, ErrorCode::InsufficientDeposit);
+        deposit.amount -= amount;
+
+        let mint = deposit.mint;
+        let bump = ctx.bumps.deposit_vault;
+        let signer_seeds: &[&[u8]] = &[b"deposit_vault", mint.as_ref(), &[bump]];
+        let signer = &[signer_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.deposit_vault.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.deposit_vault.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn delegate(
+        ctx: Context<Delegate>,
+        mint: Pubkey,
+        delegate: Pubkey,
+        scope: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        require_keys_neq!(delegate, ctx.accounts.delegator.key(), ErrorCode::BadDelegation);
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.delegator = ctx.accounts.delegator.key();
+        delegation.delegate = delegate;
+        delegation.mint = mint;
+        delegation.scope = scope;
+        delegation.expires_at = expires_at;
+        delegation.bump = ctx.bumps.delegation;
+        Ok(())
+    }
+
+    pub fn undelegate(_ctx: Context<Undelegate>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_governance_proposal(
+        ctx: Context<CreateGovernanceProposal>,
+        proposal_text: String,
+        revision_number: u64,
+        nonce: u64,
+        actions: Vec<ActionParams>,
+        atomic_actions: bool,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        require!(
+            proposal_text.len() <= MAX_GOV_PROPOSAL_TEXT_LEN,
+            ErrorCode::GovernanceTextTooLong
+        );
+        require!(revision_number == 0, ErrorCode::BadRevisionNumber);
+        check_actions(&ctx.accounts.treasury, &actions)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.authority = ctx.accounts.user.key();
+        proposal.status = 0;
+        proposal.nonce = nonce;
+        proposal.revision_count = 1;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.votes_abstain = 0;
+        proposal.judge_approve = 0;
+        proposal.judge_reject = 0;
+        proposal.judge_needs = 0;
+        proposal.final_verdict = 0;
+        proposal.proposal_text = proposal_text.clone();
+        proposal.atomic_actions = atomic_actions;
+        proposal.actions = actions.clone();
+        proposal.instruction_count = 0;
+        proposal.spent_lamports = 0;
+
+        let params = &ctx.accounts.governance_config.params;
+        proposal.judge_panel_size = params.judge_panel_size;
+        proposal.judge_threshold = params.judge_threshold();
+        proposal.decision_policy = params.decision_policy;
+        proposal.quorum_votes = params.quorum_votes;
+        proposal.approval_ratio_bps = params.approval_ratio_bps;
+        proposal.judge_weight_bps = params.judge_weight_bps;
+        proposal.governing_mint = params.governing_mint;
+        proposal.judge_verdict = 0;
+        proposal.vote_verdict = 0;
+        proposal.secret_ballot = params.secret_ballot;
+        proposal.unrevealed_votes = 0;
+        proposal.unrevealed_weight = 0;
+        proposal.judge_commits = 0;
+        proposal.unrevealed_judges = 0;
+
+        let now = Clock::get()?.unix_timestamp;
+        proposal.voting_starts_at = now;
+        proposal.voting_ends_at = now.checked_add(params.voting_period_secs).unwrap();
+        proposal.reveal_ends_at = if params.secret_ballot {
+            proposal
+                .voting_ends_at
+                .checked_add(params.reveal_period_secs)
+                .unwrap()
+        } else {
+            proposal.voting_ends_at
+        };
+        proposal.judging_ends_at = proposal
+            .reveal_ends_at
+            .checked_add(params.judging_period_secs)
+            .unwrap();
+        proposal.judge_reveal_ends_at = proposal
+            .judging_ends_at
+            .checked_add(params.judge_reveal_period_secs)
+            .unwrap();
+
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            authority: proposal.authority,
+            nonce,
+            text_hash: hashv(&[proposal_text.as_bytes()]).to_bytes(),
+            actions,
+            atomic_actions,
+            decision_policy: proposal.decision_policy,
+            judge_panel_size: proposal.judge_panel_size,
+            judge_threshold: proposal.judge_threshold,
+            governing_mint: proposal.governing_mint,
+            secret_ballot: proposal.secret_ballot,
+            voting_ends_at: proposal.voting_ends_at,
+            reveal_ends_at: proposal.reveal_ends_at,
+            judging_ends_at: proposal.judging_ends_at,
+            judge_reveal_ends_at: proposal.judge_reveal_ends_at,
+        });
+
+        let revision = &mut ctx.accounts.revision;
+        revision.proposal = proposal.key();
+        revision.revision_number = 0;
+        revision.text = proposal_text;
+
+        Ok(())
+    }
+
+    pub fn add_revision(
+        ctx: Context<AddRevision>,
+        revision_number: u64,
+        revision_text: String,
+        actions: Vec<ActionParams>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        require!(
+            revision_text.len() <= MAX_REVISION_TEXT_LEN,
+            ErrorCode::GovernanceTextTooLong
+        );
+        check_actions(&ctx.accounts.treasury, &actions)?;
+        let proposal = &mut ctx.accounts.proposal;
         require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
-        require!(choice >= 1 && choice <= 3, ErrorCode::BadVoteChoice);
+        require!(proposal.voting_open(Clock::get()?.unix_timestamp), ErrorCode::VotingClosed);
+        require!(revision_number == proposal.revision_count, ErrorCode::BadRevisionNumber);
+        require_keys_eq!(proposal.authority, ctx.accounts.user.key(), ErrorCode::NotAuthority);
+        if actions != proposal.actions {
+            require!(!proposal.has_votes(), ErrorCode::VotesAlreadyCast);
+        }
 
-        match choice {
-            1 => proposal.votes_for = proposal.votes_for.checked_add(1).unwrap(),
-            2 => proposal.votes_against = proposal.votes_against.checked_add(1).unwrap(),
-            3 => proposal.votes_abstain = proposal.votes_abstain.checked_add(1).unwrap(),
-            _ => {}
+        proposal.revision_count = proposal.revision_count.checked_add(1).unwrap();
+        proposal.proposal_text = revision_text.clone();
+        proposal.actions = actions.clone();
+
+        let revision = &mut ctx.accounts.revision;
+        revision.proposal = proposal.key();
+        revision.revision_number = revision_number;
+        emit!(RevisionAdded {
+            proposal: revision.proposal,
+            revision_number,
+            text_hash: hashv(&[revision_text.as_bytes()]).to_bytes(),
+            actions,
+        });
+        revision.text = revision_text;
+
+        Ok(())
+    }
+
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+        choice: u8,
+    ) -> Result<()> {
+        require!((1..=3).contains(&choice), ErrorCode::BadVoteChoice);
+        require!(!ctx.accounts.proposal.secret_ballot, ErrorCode::SecretBallot);
+        record_vote(ctx, choice, [0u8; 32])
+    }
+
+    pub fn commit_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        require!(ctx.accounts.proposal.secret_ballot, ErrorCode::NotSecretBallot);
+        require!(commitment != [0u8; 32], ErrorCode::BadCommitment);
+        record_vote(ctx, 0, commitment)
+    }
+
+    pub fn reveal_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeVote<'info>>,
+        choice: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+        require!(proposal.secret_ballot, ErrorCode::NotSecretBallot);
+        require!((1..=3).contains(&choice), ErrorCode::BadVoteChoice);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= proposal.voting_ends_at && now < proposal.reveal_ends_at,
+            ErrorCode::RevealClosed
+        );
+
+        let record = &mut ctx.accounts.vote_record;
+        require!(record.delegate == Pubkey::default(), ErrorCode::DelegatedVote);
+        require!(record.choice == 0, ErrorCode::AlreadyRevealed);
+        let expected = vote_commitment(choice, &salt, &record.voter);
+        require!(record.commitment == expected, ErrorCode::CommitmentMismatch);
+
+        let weight = record.weight.checked_add(record.delegated_weight).unwrap();
+        proposal.unrevealed_votes = proposal.unrevealed_votes.checked_sub(1).unwrap();
+        proposal.unrevealed_weight = proposal.unrevealed_weight.checked_sub(weight).unwrap();
+        proposal.add_votes(choice, weight);
+        record.choice = choice;
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            voter: record.voter,
+            choice,
+            weight: record.weight,
+            delegated_weight: record.delegated_weight,
+            commitment: record.commitment,
+        });
+
+        for mut delegator_record in delegator_vote_records(
+            ctx.remaining_accounts,
+            record,
+            proposal.key(),
+            ctx.program_id,
+        )? {
+            delegator_record.choice = choice;
+            delegator_record.exit(ctx.program_id)?;
         }
 
+        Ok(())
+    }
+
+    pub fn change_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeVote<'info>>,
+        choice: u8,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+        require!((1..=3).contains(&choice), ErrorCode::BadVoteChoice);
+        require!(!proposal.secret_ballot, ErrorCode::SecretBallot);
+        require!(proposal.voting_open(Clock::get()?.unix_timestamp), ErrorCode::VotingClosed);
+
         let record = &mut ctx.accounts.vote_record;
-        record.proposal = proposal.key();
-        record.voter = ctx.accounts.voter.key();
+        require!(record.delegate == Pubkey::default(), ErrorCode::DelegatedVote);
+        let weight = record.weight.checked_add(record.delegated_weight).unwrap();
+        proposal.remove_votes(record.choice, weight);
+        proposal.add_votes(choice, weight);
         record.choice = choice;
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            voter: record.voter,
+            choice,
+            weight: record.weight,
+            delegated_weight: record.delegated_weight,
+            commitment: record.commitment,
+        });
+
+        for mut delegator_record in delegator_vote_records(
+            ctx.remaining_accounts,
+            record,
+            proposal.key(),
+            ctx.program_id,
+        )? {
+            delegator_record.choice = choice;
+            delegator_record.exit(ctx.program_id)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawVote<'info>>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+        require!(proposal.voting_open(Clock::get()?.unix_timestamp), ErrorCode::VotingClosed);
+
+        let record = &ctx.accounts.vote_record;
+        require!(record.delegate == Pubkey::default(), ErrorCode::DelegatedVote);
+        let weight = record.weight.checked_add(record.delegated_weight).unwrap();
+        if record.choice == 0 {
+            proposal.unrevealed_votes = proposal.unrevealed_votes.checked_sub(1).unwrap();
+            proposal.unrevealed_weight = proposal.unrevealed_weight.checked_sub(weight).unwrap();
+        } else {
+            proposal.remove_votes(record.choice, weight);
+        }
+        emit!(VoteWithdrawn {
+            proposal: proposal.key(),
+            voter: record.voter,
+            choice: record.choice,
+            weight: record.weight,
+            delegated_weight: record.delegated_weight,
+        });
+
+        for delegator_record in delegator_vote_records(
+            ctx.remaining_accounts,
+            record,
+            proposal.key(),
+            ctx.program_id,
+        )? {
+            delegator_record.close(ctx.accounts.voter.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn submit_judge_result(
+        ctx: Context<SubmitJudgeResult>,
+        commitment: [u8; 32],
+        prompt_hash: [u8; 32],
+        model_id: String,
+        rationale_hash: [u8; 32],
+        receipt_root: [u8; 32],
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        require!(ctx.accounts.judge_entry.active, ErrorCode::JudgeNotActive);
+        require!(commitment != [0u8; 32], ErrorCode::BadCommitment);
+        require!(
+            model_id.len() <= MAX_MODEL_ID_LEN,
+            ErrorCode::ModelIdTooLong
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= proposal.reveal_ends_at, ErrorCode::VotingNotEnded);
+        require!(now < proposal.judging_ends_at, ErrorCode::JudgingClosed);
+        require!(
+            proposal.judge_commits < proposal.judge_panel_size,
+            ErrorCode::TooManyJudges
+        );
+        proposal.judge_commits += 1;
+
+        let result = &mut ctx.accounts.judge_result;
+        result.proposal = proposal.key();
+        result.judge = ctx.accounts.judge.key();
+        result.verdict = 0;
+        result.commitment = commitment;
+        result.revealed = false;
+        result.prompt_hash = prompt_hash;
+        result.model_id = model_id;
+        result.rationale_hash = rationale_hash;
+        result.receipt_root = receipt_root;
+        result.payer = ctx.accounts.payer.key();
+
+        emit!(JudgeResultSubmitted {
+            proposal: result.proposal,
+            judge: result.judge,
+            commitment,
+            prompt_hash,
+            model_id: result.model_id.clone(),
+            rationale_hash,
+            receipt_root,
+        });
 
         Ok(())
     }
 
-    pub fn submit_judge_result(ctx: Context<SubmitJudgeResult>, verdict: u8) -> Result<()> {
+    pub fn reveal_judge_result(
+        ctx: Context<RevealJudgeResult>,
+        verdict: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
         let proposal = &mut ctx.accounts.proposal;
         require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
-        require!(verdict >= 1 && verdict <= 3, ErrorCode::BadJudgeVerdict);
+        require!((1..=3).contains(&verdict), ErrorCode::BadJudgeVerdict);
+        let now = Clock::get()?.unix_timestamp;
+        require!(proposal.judge_reveal_open(now), ErrorCode::RevealNotOpen);
+        require!(now < proposal.judge_reveal_ends_at, ErrorCode::RevealClosed);
 
-        let total = proposal.judge_approve as u16
-            + proposal.judge_reject as u16
-            + proposal.judge_needs as u16;
-        require!(total < 3, ErrorCode::TooManyJudges);
+        let result = &mut ctx.accounts.judge_result;
+        require!(!result.revealed, ErrorCode::AlreadyRevealed);
+        require!(
+            judge_commitment(verdict, &salt, &result.judge) == result.commitment,
+            ErrorCode::CommitmentMismatch
+        );
 
         match verdict {
             1 => proposal.judge_approve = proposal.judge_approve.saturating_add(1),
@@ -293,180 +1390,1776 @@ pub mod ambient_svm_hello {
             3 => proposal.judge_needs = proposal.judge_needs.saturating_add(1),
             _ => {}
         }
-
-        let result = &mut ctx.accounts.judge_result;
-        result.proposal = proposal.key();
-        result.judge = ctx.accounts.judge.key();
         result.verdict = verdict;
+        result.revealed = true;
+
+        emit!(JudgeResultRevealed {
+            proposal: result.proposal,
+            judge: result.judge,
+            verdict,
+        });
 
         Ok(())
     }
 
-    pub fn finalize_consensus(ctx: Context<FinalizeConsensus>) -> Result<()> {
+    pub fn finalize_consensus<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeConsensus<'info>>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
         let proposal = &mut ctx.accounts.proposal;
         require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
 
-        let total = proposal.judge_approve as u16
-            + proposal.judge_reject as u16
-            + proposal.judge_needs as u16;
-        require!(total == 3, ErrorCode::NotEnoughJudges);
+        let revealed = proposal.judges_revealed();
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= proposal.reveal_ends_at, ErrorCode::VotingNotEnded);
+        require!(
+            proposal.decision_policy == POLICY_VOTES_ONLY
+                || revealed == proposal.judge_panel_size as u16
+                || (now >= proposal.judging_ends_at
+                    && revealed == proposal.judge_commits as u16)
+                || now >= proposal.judge_reveal_ends_at,
+            ErrorCode::NotEnoughJudges
+        );
+        proposal.unrevealed_judges = (proposal.judge_commits as u16 - revealed) as u8;
 
-        let verdict = if proposal.judge_approve >= 2 {
-            1
-        } else if proposal.judge_reject >= 2 {
-            2
-        } else {
-            3
+        let judge_verdict = proposal.judge_outcome();
+        let vote_verdict = proposal.vote_outcome();
+        let verdict = match proposal.decision_policy {
+            POLICY_VOTES_ONLY => vote_verdict,
+            POLICY_BOTH_APPROVE => {
+                if judge_verdict == 1 && vote_verdict == 1 {
+                    1
+                } else if judge_verdict == 2 || vote_verdict == 2 {
+                    2
+                } else {
+                    3
+                }
+            }
+            POLICY_WEIGHTED => proposal.weighted_outcome(),
+            _ => judge_verdict,
         };
 
+        proposal.judge_verdict = judge_verdict;
+        proposal.vote_verdict = vote_verdict;
         proposal.final_verdict = verdict;
         proposal.status = 1;
 
-        let action = &mut ctx.accounts.action_request;
-        action.proposal = proposal.key();
-        action.status = if verdict == 1 { 0 } else { 2 };
-        action.amount_lamports = ACTION_LAMPORTS;
-        action.recipient = proposal.authority;
-        action.executor = Pubkey::default();
+        let action_status = if verdict == 1 { 0 } else { 2 };
+        create_action_requests(
+            ctx.remaining_accounts,
+            proposal.key(),
+            &proposal.actions,
+            action_status,
+            &ctx.accounts.finalizer,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        emit!(ConsensusFinalized {
+            proposal: proposal.key(),
+            final_verdict: verdict,
+            judge_verdict,
+            vote_verdict,
+            judge_approve: proposal.judge_approve,
+            judge_reject: proposal.judge_reject,
+            judge_needs: proposal.judge_needs,
+            unrevealed_judges: proposal.unrevealed_judges,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+            unrevealed_votes: proposal.unrevealed_votes,
+            action_count: proposal.actions.len() as u8,
+            action_status,
+        });
 
         Ok(())
     }
 
-    pub fn complete_action(ctx: Context<CompleteAction>) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+    pub fn complete_action(ctx: Context<CompleteAction>, index: u8) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_TREASURY)?;
+        let proposal = &ctx.accounts.proposal;
         require!(proposal.final_verdict == 1, ErrorCode::ActionNotApproved);
+        require!(!proposal.atomic_actions, ErrorCode::AtomicActions);
 
         let action = &mut ctx.accounts.action_request;
         require!(action.status == 0, ErrorCode::ActionNotPending);
         require_keys_eq!(action.recipient, ctx.accounts.recipient.key(), ErrorCode::BadRecipient);
+        ctx.accounts
+            .proposal
+            .record_spend(action.amount_lamports, ctx.accounts.treasury.max_action_lamports)?;
 
-        let bump = ctx.bumps.treasury_vault;
-        let signer_seeds: &[&[u8]] = &[b"treasury_vault", &[bump]];
-        let signer = &[signer_seeds];
+        pay_from_treasury(
+            &ctx.accounts.treasury_vault,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.treasury_vault,
+            action.amount_lamports,
+        )?;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.treasury_vault.to_account_info(),
-                to: ctx.accounts.recipient.to_account_info(),
+        action.status = 1;
+        action.executor = ctx.accounts.executor.key();
+
+        emit!(ActionCompleted {
+            proposal: action.proposal,
+            index,
+            recipient: action.recipient,
+            amount_lamports: action.amount_lamports,
+            executor: action.executor,
+        });
+
+        Ok(())
+    }
+
+    pub fn complete_all_actions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteAllActions<'info>>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_TREASURY)?;
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.final_verdict == 1, ErrorCode::ActionNotApproved);
+        require!(
+            ctx.remaining_accounts.len() == proposal.actions.len() * 2,
+            ErrorCode::BadActionAccounts
+        );
+
+        let proposal_key = proposal.key();
+        let executor = ctx.accounts.executor.key();
+        let mut total: u64 = 0;
+        for (index, accounts) in ctx.remaining_accounts.chunks(2).enumerate() {
+            let (action_key, _) = Pubkey::find_program_address(
+                &[b"action", proposal_key.as_ref(), &[index as u8]],
+                ctx.program_id,
+            );
+            require_keys_eq!(accounts[0].key(), action_key, ErrorCode::BadActionAccounts);
+            let mut action = Account::<ActionRequest>::try_from(&accounts[0])?;
+            require!(action.status == 0, ErrorCode::ActionNotPending);
+            require_keys_eq!(action.recipient, accounts[1].key(), ErrorCode::BadRecipient);
+            total = total.checked_add(action.amount_lamports).unwrap();
+
+            pay_from_treasury(
+                &ctx.accounts.treasury_vault,
+                &accounts[1],
+                &ctx.accounts.system_program,
+                ctx.bumps.treasury_vault,
+                action.amount_lamports,
+            )?;
+            action.status = 1;
+            action.executor = executor;
+            action.exit(ctx.program_id)?;
+
+            emit!(ActionCompleted {
+                proposal: proposal_key,
+                index: index as u8,
+                recipient: action.recipient,
+                amount_lamports: action.amount_lamports,
+                executor,
+            });
+        }
+        ctx.accounts
+            .proposal
+            .record_spend(total, ctx.accounts.treasury.max_action_lamports)?;
+
+        Ok(())
+    }
+
+    pub fn insert_proposal_instruction(
+        ctx: Context<InsertProposalInstruction>,
+        index: u8,
+        program_id: Pubkey,
+        accounts: Vec<InstructionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+        let proposal = &mut ctx.accounts.proposal;
+        require_keys_eq!(proposal.authority, ctx.accounts.authority.key(), ErrorCode::NotAuthority);
+        require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+        require!(proposal.voting_open(Clock::get()?.unix_timestamp), ErrorCode::VotingClosed);
+        require!(!proposal.has_votes(), ErrorCode::VotesAlreadyCast);
+        require!(index == proposal.instruction_count, ErrorCode::BadInstructionIndex);
+        require!(index < MAX_PROPOSAL_INSTRUCTIONS, ErrorCode::TooManyInstructions);
+        require!(
+            accounts.len() <= MAX_INSTRUCTION_ACCOUNTS,
+            ErrorCode::TooManyInstructionAccounts
+        );
+        require!(
+            data.len() <= MAX_INSTRUCTION_DATA_LEN,
+            ErrorCode::InstructionDataTooLong
+        );
+        let (vault, _) = Pubkey::find_program_address(&[b"treasury_vault"], ctx.program_id);
+        for meta in &accounts {
+            require!(!meta.is_signer || meta.pubkey == vault, ErrorCode::BadInstructionSigner);
+        }
+        proposal.instruction_count += 1;
+
+        let instruction = &mut ctx.accounts.proposal_instruction;
+        instruction.proposal = proposal.key();
+        instruction.index = index;
+        instruction.executed = false;
+        instruction.executor = Pubkey::default();
+        instruction.program_id = program_id;
+        instruction.accounts = accounts;
+        instruction.data = data;
+
+        emit!(ProposalInstructionInserted {
+            proposal: instruction.proposal,
+            index,
+            program_id,
+            account_count: instruction.accounts.len() as u8,
+            data_hash: hashv(&[&instruction.data]).to_bytes(),
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_instruction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteInstruction<'info>>,
+        index: u8,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_TREASURY)?;
+        require!(
+            ctx.accounts.proposal.final_verdict == 1,
+            ErrorCode::ActionNotApproved
+        );
+
+        let instruction = &mut ctx.accounts.proposal_instruction;
+        require!(!instruction.executed, ErrorCode::InstructionExecuted);
+        let remaining = ctx.remaining_accounts;
+        require!(
+            remaining.len() == 1 + instruction.accounts.len(),
+            ErrorCode::BadInstructionAccounts
+        );
+        require_keys_eq!(
+            remaining[0].key(),
+            instruction.program_id,
+            ErrorCode::BadInstructionAccounts
+        );
+
+        let mut metas = Vec::with_capacity(instruction.accounts.len());
+        let mut infos = Vec::with_capacity(remaining.len());
+        for (meta, info) in instruction.accounts.iter().zip(&remaining[1..]) {
+            require_keys_eq!(info.key(), meta.pubkey, ErrorCode::BadInstructionAccounts);
+            metas.push(AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            });
+            infos.push(info.clone());
+        }
+        infos.push(remaining[0].clone());
+
+        instruction.executed = true;
+        instruction.executor = ctx.accounts.executor.key();
+        instruction.exit(ctx.program_id)?;
+
+        let vault_before = ctx.accounts.treasury_vault.lamports();
+        invoke_signed(
+            &Instruction {
+                program_id: instruction.program_id,
+                accounts: metas,
+                data: instruction.data.clone(),
             },
-            signer,
+            &infos,
+            &[&[b"treasury_vault", &[ctx.bumps.treasury_vault]]],
+        )?;
+        let vault = ctx.accounts.treasury_vault.to_account_info();
+        require!(
+            vault.owner == &system_program::ID && vault.data_is_empty(),
+            ErrorCode::TreasuryVaultModified
         );
-        system_program::transfer(cpi_ctx, action.amount_lamports)?;
+        let spent = vault_before.saturating_sub(vault.lamports());
+        ctx.accounts
+            .proposal
+            .record_spend(spent, ctx.accounts.treasury.max_action_lamports)?;
 
-        action.status = 1;
-        action.executor = ctx.accounts.executor.key();
+        emit!(ProposalInstructionExecuted {
+            proposal: instruction.proposal,
+            index,
+            program_id: instruction.program_id,
+            executor: instruction.executor,
+            vault_spent_lamports: spent,
+        });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitConfig<'info> {
+    pub fn close_judge_request(ctx: Context<CloseJudgeRequest>) -> Result<()> {
+        let req = &ctx.accounts.request;
+        require_keys_eq!(
+            req.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRequestAuthority
+        );
+        require!(req.status == 1, ErrorCode::NotClosable);
+        emit!(JudgeRequestClosed {
+            request: req.key(),
+            authority: req.authority,
+            decision: req.decision,
+            response_hash: req.response_hash,
+            receipt_root: req.receipt_root,
+            prompt_hash: req.prompt_hash,
+        });
+        Ok(())
+    }
+
+    pub fn close_relayer_result(ctx: Context<CloseRelayerResult>) -> Result<()> {
+        let request = &ctx.accounts.request;
+        if !request.data_is_empty() && *request.owner == crate::ID {
+            let req = JudgeRequest::try_deserialize(&mut &request.try_borrow_data()?[..])?;
+            require!(req.status != 0, ErrorCode::NotClosable);
+        }
+        let result = &ctx.accounts.relayer_result;
+        emit!(RelayerResultClosed {
+            request: result.request,
+            relayer: result.relayer,
+            decision: result.decision,
+            response_hash: result.response_hash,
+            receipt_root: result.receipt_root,
+        });
+        Ok(())
+    }
+
+    pub fn close_proposal_request(ctx: Context<CloseProposalRequest>) -> Result<()> {
+        let req = &ctx.accounts.request;
+        require_keys_eq!(
+            req.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotRequestAuthority
+        );
+        require!(req.status == 3, ErrorCode::NotClosable);
+        emit!(ProposalRequestClosed {
+            request: req.key(),
+            authority: req.authority,
+            verdict_code: req.verdict_code,
+            summary_hash: req.summary_hash,
+            receipt_root: req.receipt_root,
+            prompt_hash: req.prompt_hash,
+        });
+        Ok(())
+    }
+
+    pub fn close_revision(ctx: Context<CloseRevision>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require_keys_eq!(
+            proposal.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotAuthority
+        );
+        require!(proposal.status == 1, ErrorCode::NotClosable);
+        let revision = &ctx.accounts.revision;
+        emit!(ProposalRevisionClosed {
+            proposal: revision.proposal,
+            revision_number: revision.revision_number,
+            text_hash: hashv(&[revision.text.as_bytes()]).to_bytes(),
+        });
+        Ok(())
+    }
+
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        require!(ctx.accounts.proposal.status == 1, ErrorCode::NotClosable);
+        let record = &ctx.accounts.vote_record;
+        let payer = if record.delegate == Pubkey::default() {
+            record.voter
+        } else {
+            record.delegate
+        };
+        require_keys_eq!(payer, ctx.accounts.receiver.key(), ErrorCode::NotClosable);
+        emit!(VoteRecordClosed {
+            proposal: record.proposal,
+            voter: record.voter,
+            choice: record.choice,
+            weight: record.weight,
+            delegated_weight: record.delegated_weight,
+            commitment: record.commitment,
+        });
+        Ok(())
+    }
+
+    pub fn close_judge_result(ctx: Context<CloseJudgeResult>) -> Result<()> {
+        require!(ctx.accounts.proposal.status == 1, ErrorCode::NotClosable);
+        let result = &ctx.accounts.judge_result;
+        require_keys_eq!(result.payer, ctx.accounts.receiver.key(), ErrorCode::NotClosable);
+        emit!(JudgeResultClosed {
+            proposal: result.proposal,
+            judge: result.judge,
+            verdict: result.verdict,
+            revealed: result.revealed,
+            commitment: result.commitment,
+            prompt_hash: result.prompt_hash,
+            rationale_hash: result.rationale_hash,
+            receipt_root: result.receipt_root,
+        });
+        Ok(())
+    }
+
+    pub fn close_action_request(ctx: Context<CloseActionRequest>, index: u8) -> Result<()> {
+        let action = &ctx.accounts.action_request;
+        require!(action.status != 0, ErrorCode::NotClosable);
+        require_keys_eq!(action.payer, ctx.accounts.receiver.key(), ErrorCode::NotClosable);
+        emit!(ActionRequestClosed {
+            proposal: action.proposal,
+            index,
+            status: action.status,
+            amount_lamports: action.amount_lamports,
+            recipient: action.recipient,
+            executor: action.executor,
+        });
+        Ok(())
+    }
+
+    pub fn close_proposal_instruction(
+        ctx: Context<CloseProposalInstruction>,
+        index: u8,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require_keys_eq!(
+            proposal.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::NotAuthority
+        );
+        require!(proposal.status == 1, ErrorCode::NotClosable);
+        let instruction = &ctx.accounts.proposal_instruction;
+        require!(
+            instruction.executed || proposal.final_verdict != 1,
+            ErrorCode::NotClosable
+        );
+        emit!(ProposalInstructionClosed {
+            proposal: instruction.proposal,
+            index,
+            executed: instruction.executed,
+            program_id: instruction.program_id,
+            data_hash: hashv(&[&instruction.data]).to_bytes(),
+        });
+        Ok(())
+    }
+}
+
+fn require_not_paused(config: &Config, subsystem: u8) -> Result<()> {
+    require!(
+        !config.paused && config.pause_flags & subsystem == 0,
+        ErrorCode::Paused
+    );
+    Ok(())
+}
+
+fn record_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+    choice: u8,
+    commitment: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, PAUSE_GOVERNANCE)?;
+    let proposal = &ctx.accounts.proposal;
+    require!(proposal.status == 0, ErrorCode::AlreadyFinalized);
+    require!(proposal.voting_open(Clock::get()?.unix_timestamp), ErrorCode::VotingClosed);
+
+    let own_weight = if proposal.governing_mint == Pubkey::default() {
+        1
+    } else {
+        match ctx.accounts.voter_deposit.as_mut() {
+            Some(deposit) => {
+                deposit.locked_until = deposit.locked_until.max(proposal.reveal_ends_at);
+                deposit.amount
+            }
+            None => 0,
+        }
+    };
+
+    let (delegators, delegated_weight) = record_delegated_votes(
+        ctx.remaining_accounts,
+        &ctx.accounts.proposal,
+        &ctx.accounts.voter,
+        &ctx.accounts.system_program,
+        choice,
+        ctx.program_id,
+    )?;
+    let weight = own_weight.checked_add(delegated_weight).unwrap();
+    require!(weight > 0, ErrorCode::NoVotingWeight);
+
+    let proposal = &mut ctx.accounts.proposal;
+    if choice == 0 {
+        proposal.unrevealed_votes = proposal.unrevealed_votes.checked_add(1).unwrap();
+        proposal.unrevealed_weight = proposal.unrevealed_weight.checked_add(weight).unwrap();
+    } else {
+        proposal.add_votes(choice, weight);
+    }
+
+    let record = &mut ctx.accounts.vote_record;
+    record.proposal = proposal.key();
+    record.voter = ctx.accounts.voter.key();
+    record.choice = choice;
+    record.weight = own_weight;
+    record.delegate = Pubkey::default();
+    record.delegated_weight = delegated_weight;
+    record.delegators = delegators;
+    record.commitment = commitment;
+
+    emit!(VoteCast {
+        proposal: record.proposal,
+        voter: record.voter,
+        choice,
+        weight: own_weight,
+        delegated_weight,
+        commitment,
+    });
+
+    Ok(())
+}
+
+pub fn vote_commitment(choice: u8, salt: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    hashv(&[&[choice], salt.as_ref(), voter.as_ref()]).to_bytes()
+}
+
+pub fn judge_commitment(verdict: u8, salt: &[u8; 32], judge: &Pubkey) -> [u8; 32] {
+    hashv(&[&[verdict], salt.as_ref(), judge.as_ref()]).to_bytes()
+}
+
+fn check_actions(treasury: &Treasury, actions: &[ActionParams]) -> Result<()> {
+    require!(actions.len() <= MAX_PROPOSAL_ACTIONS, ErrorCode::TooManyActions);
+    let mut total: u64 = 0;
+    for action in actions {
+        require!(action.recipient != Pubkey::default(), ErrorCode::BadRecipient);
+        total = total.checked_add(action.amount_lamports).unwrap();
+    }
+    require!(total <= treasury.max_action_lamports, ErrorCode::ActionOverCap);
+    Ok(())
+}
+
+fn create_action_requests<'info>(
+    remaining: &'info [AccountInfo<'info>],
+    proposal_key: Pubkey,
+    actions: &[ActionParams],
+    status: u8,
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    require!(remaining.len() == actions.len(), ErrorCode::BadActionAccounts);
+    for (index, (info, params)) in remaining.iter().zip(actions.iter()).enumerate() {
+        let index = index as u8;
+        let (action_key, bump) = Pubkey::find_program_address(
+            &[b"action", proposal_key.as_ref(), &[index]],
+            program_id,
+        );
+        require_keys_eq!(info.key(), action_key, ErrorCode::BadActionAccounts);
+        create_pda_account(
+            payer,
+            info,
+            system,
+            ActionRequest::space(),
+            &[b"action", proposal_key.as_ref(), &[index], &[bump]],
+            program_id,
+        )?;
+        let action = ActionRequest {
+            proposal: proposal_key,
+            status,
+            amount_lamports: params.amount_lamports,
+            recipient: params.recipient,
+            executor: Pubkey::default(),
+            index,
+            payer: payer.key(),
+        };
+        action.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+fn pay_from_treasury<'info>(
+    vault: &SystemAccount<'info>,
+    recipient: &AccountInfo<'info>,
+    system: &Program<'info, System>,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[u8]] = &[b"treasury_vault", &[bump]];
+    let signer = &[signer_seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        system.to_account_info(),
+        system_program::Transfer {
+            from: vault.to_account_info(),
+            to: recipient.clone(),
+        },
+        signer,
+    );
+    system_program::transfer(cpi_ctx, amount)
+}
+
+fn record_delegated_votes<'info>(
+    remaining: &'info [AccountInfo<'info>],
+    proposal: &Account<'info, Proposal>,
+    voter: &Signer<'info>,
+    system: &Program<'info, System>,
+    choice: u8,
+    program_id: &Pubkey,
+) -> Result<(Vec<Pubkey>, u64)> {
+    let per_delegator = if proposal.governing_mint == Pubkey::default() {
+        2
+    } else {
+        3
+    };
+    require!(
+        remaining.len() % per_delegator == 0,
+        ErrorCode::BadDelegationAccounts
+    );
+    require!(
+        remaining.len() / per_delegator <= MAX_DELEGATORS_PER_VOTE,
+        ErrorCode::TooManyDelegators
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal_key = proposal.key();
+    let mut delegators = Vec::new();
+    let mut total: u64 = 0;
+    for accounts in remaining.chunks(per_delegator) {
+        let delegation = Account::<Delegation>::try_from(&accounts[0])?;
+        require_keys_eq!(delegation.delegate, voter.key(), ErrorCode::BadDelegation);
+        require_keys_eq!(delegation.mint, proposal.governing_mint, ErrorCode::BadDelegation);
+        require!(
+            delegation.scope == Pubkey::default() || delegation.scope == proposal_key,
+            ErrorCode::BadDelegation
+        );
+        require!(
+            delegation.expires_at == 0 || now < delegation.expires_at,
+            ErrorCode::DelegationExpired
+        );
+        let delegator = delegation.delegator;
+
+        let weight = if per_delegator == 2 {
+            1
+        } else {
+            let mut deposit = Account::<VoterDeposit>::try_from(&accounts[2])?;
+            require_keys_eq!(deposit.owner, delegator, ErrorCode::BadDelegationAccounts);
+            require_keys_eq!(
+                deposit.mint,
+                proposal.governing_mint,
+                ErrorCode::BadDelegationAccounts
+            );
+            deposit.locked_until = deposit.locked_until.max(proposal.reveal_ends_at);
+            let amount = deposit.amount;
+            deposit.exit(program_id)?;
+            amount
+        };
+
+        // The delegator's own vote record marks their power as used on this proposal.
+        let record_info = &accounts[1];
+        let (record_key, bump) = Pubkey::find_program_address(
+            &[b"vote", proposal_key.as_ref(), delegator.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(record_info.key(), record_key, ErrorCode::BadDelegationAccounts);
+        require!(
+            record_info.owner == &system_program::ID && record_info.data_is_empty(),
+            ErrorCode::AlreadyVoted
+        );
+        create_pda_account(
+            voter,
+            record_info,
+            system,
+            VoteRecord::space(),
+            &[b"vote", proposal_key.as_ref(), delegator.as_ref(), &[bump]],
+            program_id,
+        )?;
+        let record = VoteRecord {
+            proposal: proposal_key,
+            voter: delegator,
+            choice,
+            weight,
+            delegate: voter.key(),
+            delegated_weight: 0,
+            delegators: Vec::new(),
+            commitment: [0u8; 32],
+        };
+        record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        delegators.push(delegator);
+        total = total.checked_add(weight).unwrap();
+    }
+    Ok((delegators, total))
+}
+
+fn delegator_vote_records<'info>(
+    remaining: &'info [AccountInfo<'info>],
+    record: &VoteRecord,
+    proposal_key: Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Account<'info, VoteRecord>>> {
+    require!(
+        remaining.len() == record.delegators.len(),
+        ErrorCode::BadDelegationAccounts
+    );
+    let mut records = Vec::with_capacity(remaining.len());
+    for (info, delegator) in remaining.iter().zip(record.delegators.iter()) {
+        let (record_key, _) = Pubkey::find_program_address(
+            &[b"vote", proposal_key.as_ref(), delegator.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(info.key(), record_key, ErrorCode::BadDelegationAccounts);
+        records.push(Account::<VoteRecord>::try_from(info)?);
+    }
+    Ok(records)
+}
+
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    owner: &Pubkey,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer = &[seeds];
+    let current = target.lamports();
+    if current == 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system.clone(),
+            system_program::CreateAccount {
+                from: payer.clone(),
+                to: target.clone(),
+            },
+            signer,
+        );
+        return system_program::create_account(cpi_ctx, rent, space as u64, owner);
+    }
+
+    if rent > current {
+        let cpi_ctx = CpiContext::new(
+            system.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: target.clone(),
+            },
+        );
+        system_program::transfer(cpi_ctx, rent - current)?;
+    }
+    let cpi_ctx = CpiContext::new_with_signer(
+        system.clone(),
+        system_program::Allocate {
+            account_to_allocate: target.clone(),
+        },
+        signer,
+    );
+    system_program::allocate(cpi_ctx, space as u64)?;
+    let cpi_ctx = CpiContext::new_with_signer(
+        system.clone(),
+        system_program::Assign {
+            account_to_assign: target.clone(),
+        },
+        signer,
+    );
+    system_program::assign(cpi_ctx, owner)
+}
+
+pub fn receipt_root_from_proof(
+    leaf: &[u8; 32],
+    leaf_index: u32,
+    proof: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    if proof.len() > MAX_RECEIPT_PROOF_DEPTH || u64::from(leaf_index) >= 1u64 << proof.len() {
+        return None;
+    }
+    let mut node = hashv(&[&[RECEIPT_LEAF_PREFIX], leaf.as_ref()]).to_bytes();
+    let mut index = leaf_index;
+    for sibling in proof {
+        node = if index & 1 == 0 {
+            hashv(&[&[RECEIPT_NODE_PREFIX], node.as_ref(), sibling.as_ref()]).to_bytes()
+        } else {
+            hashv(&[&[RECEIPT_NODE_PREFIX], sibling.as_ref(), node.as_ref()]).to_bytes()
+        };
+        index >>= 1;
+    }
+    Some(node)
+}
+
+pub fn verify_receipt_proof(
+    receipt_root: &[u8; 32],
+    leaf: &[u8; 32],
+    leaf_index: u32,
+    proof: &[[u8; 32]],
+) -> bool {
+    receipt_root_from_proof(leaf, leaf_index, proof) == Some(*receipt_root)
+}
+
+fn stored_receipt_root(info: &AccountInfo) -> Result<[u8; 32]> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::NoReceiptRoot);
+    let data = info.try_borrow_data()?;
+    if let Ok(req) = JudgeRequest::try_deserialize(&mut &data[..]) {
+        return Ok(req.receipt_root);
+    }
+    if let Ok(req) = ProposalRequest::try_deserialize(&mut &data[..]) {
+        return Ok(req.receipt_root);
+    }
+    if let Ok(result) = JudgeResult::try_deserialize(&mut &data[..]) {
+        return Ok(result.receipt_root);
+    }
+    err!(ErrorCode::NoReceiptRoot)
+}
+
+pub fn attestation_message(
+    request: &Pubkey,
+    decision: u8,
+    response_hash: &[u8; 32],
+    receipt_root: &[u8; 32],
+    prompt_hash: &[u8; 32],
+    model_id: &str,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 1 + 32 * 3 + model_id.len());
+    message.extend_from_slice(request.as_ref());
+    message.push(decision);
+    message.extend_from_slice(response_hash);
+    message.extend_from_slice(receipt_root);
+    message.extend_from_slice(prompt_hash);
+    message.extend_from_slice(model_id.as_bytes());
+    message
+}
+
+fn verify_attestation(config: &Config, instructions: &AccountInfo, message: &[u8]) -> Result<()> {
+    if config.attestors.is_empty() {
+        return Ok(());
+    }
+    let ix = get_instruction_relative(-1, instructions)
+        .map_err(|_| error!(ErrorCode::MissingAttestation))?;
+    require_keys_eq!(ix.program_id, ED25519_PROGRAM_ID, ErrorCode::MissingAttestation);
+    let attestor = ed25519_signer(&ix.data, message)?;
+    require!(config.attestors.contains(&attestor), ErrorCode::UnknownAttestor);
+    Ok(())
+}
+
+fn ed25519_signer(data: &[u8], message: &[u8]) -> Result<Pubkey> {
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::BadAttestation);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let current = u16::MAX as usize;
+    require!(
+        read_u16(4) == current && read_u16(8) == current && read_u16(14) == current,
+        ErrorCode::BadAttestation
+    );
+
+    let pubkey_offset = read_u16(6);
+    let message_offset = read_u16(10);
+    let signed = data
+        .get(message_offset..message_offset + read_u16(12))
+        .ok_or(ErrorCode::BadAttestation)?;
+    require!(signed == message, ErrorCode::BadAttestation);
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(ErrorCode::BadAttestation)?;
+    Ok(Pubkey::try_from(pubkey).unwrap())
+}
+
+pub fn callback_discriminator() -> [u8; 8] {
+    let hash = hashv(&[b"global:", CALLBACK_IX_NAME.as_bytes()]).to_bytes();
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+fn invoke_judge_callback<'info>(
+    request: &Account<'info, JudgeRequest>,
+    remaining: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<()> {
+    if request.callback_program == Pubkey::default() {
+        return Ok(());
+    }
+    require!(
+        remaining.len() == 2 + request.callback_accounts.len(),
+        ErrorCode::BadCallbackAccounts
+    );
+    let request_key = request.key();
+    let (authority, bump) = Pubkey::find_program_address(
+        &[CALLBACK_AUTHORITY_SEED, request_key.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(
+        remaining[0].key(),
+        request.callback_program,
+        ErrorCode::BadCallbackAccounts
+    );
+    require_keys_eq!(remaining[1].key(), authority, ErrorCode::BadCallbackAccounts);
+
+    request.exit(program_id)?;
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(request.key(), false),
+    ];
+    let mut infos = vec![remaining[1].clone(), request.to_account_info()];
+    for (meta, info) in request.callback_accounts.iter().zip(&remaining[2..]) {
+        require_keys_eq!(info.key(), meta.pubkey, ErrorCode::BadCallbackAccounts);
+        metas.push(if meta.is_writable {
+            AccountMeta::new(meta.pubkey, false)
+        } else {
+            AccountMeta::new_readonly(meta.pubkey, false)
+        });
+        infos.push(info.clone());
+    }
+    infos.push(remaining[0].clone());
+
+    let mut data = callback_discriminator().to_vec();
+    (
+        request.key(),
+        request.decision,
+        request.response_hash,
+        request.receipt_root,
+    )
+        .serialize(&mut data)?;
+
+    invoke_signed(
+        &Instruction {
+            program_id: request.callback_program,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[&[CALLBACK_AUTHORITY_SEED, request_key.as_ref(), &[bump]]],
+    )?;
+    Ok(())
+}
+
+fn escrow_request_fee<'info>(
+    user: &Signer<'info>,
+    request: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    fee_lamports: u64,
+) -> Result<()> {
+    if fee_lamports == 0 {
+        return Ok(());
+    }
+    let cpi_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        system_program::Transfer {
+            from: user.to_account_info(),
+            to: request.clone(),
+        },
+    );
+    system_program::transfer(cpi_ctx, fee_lamports)
+}
+
+fn pay_request_fee(request: &AccountInfo, relayer: &AccountInfo, fee_lamports: u64) -> Result<()> {
+    if fee_lamports == 0 {
+        return Ok(());
+    }
+    **request.try_borrow_mut_lamports()? -= fee_lamports;
+    **relayer.try_borrow_mut_lamports()? += fee_lamports;
+    Ok(())
+}
+
+fn check_relayer(
+    config: &Config,
+    relayer_entry: &Option<Account<RelayerEntry>>,
+    relayer: &Pubkey,
+) -> Result<()> {
+    if config.relayer == *relayer {
+        return Ok(());
+    }
+    match relayer_entry {
+        Some(entry) if entry.relayer == *relayer => Ok(()),
+        _ => err!(ErrorCode::BadRelayer),
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Config::space(),
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct AddRelayer<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = RelayerEntry::space(),
+        seeds = [b"relayer", relayer.as_ref()],
+        bump
+    )]
+    pub relayer_entry: Account<'info, RelayerEntry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"relayer", relayer_entry.relayer.as_ref()],
+        bump = relayer_entry.bump
+    )]
+    pub relayer_entry: Account<'info, RelayerEntry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(criteria: String, input_a: String, input_b: String, nonce: u64)]
+pub struct CreateJudgeRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = user,
+        space = JudgeRequest::space(),
+        seeds = [b"req", user.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, JudgeRequest>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillJudgeRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub request: Account<'info, JudgeRequest>,
+
+    #[account(
+        seeds = [b"relayer", relayer.key().as_ref()],
+        bump = relayer_entry.bump
+    )]
+    pub relayer_entry: Option<Account<'info, RelayerEntry>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: instructions sysvar, read for the Ed25519 attestation check.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitRelayerResult<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub request: Account<'info, JudgeRequest>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = RelayerResult::space(),
+        seeds = [b"relayer_result", request.key().as_ref(), relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_result: Account<'info, RelayerResult>,
+
+    #[account(
+        seeds = [b"relayer", relayer.key().as_ref()],
+        bump = relayer_entry.bump
+    )]
+    pub relayer_entry: Option<Account<'info, RelayerEntry>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: instructions sysvar, read for the Ed25519 attestation check.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeliverCallback<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub request: Account<'info, JudgeRequest>,
+}
+
+#[derive(Accounts)]
+#[instruction(source: String, proposal_id: String, proposal_text: String, nonce: u64)]
+pub struct CreateProposalRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = user,
+        space = ProposalRequest::space(),
+        seeds = [b"proposal", user.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, ProposalRequest>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillProposalRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub request: Account<'info, ProposalRequest>,
+
+    #[account(
+        seeds = [b"relayer", relayer.key().as_ref()],
+        bump = relayer_entry.bump
+    )]
+    pub relayer_entry: Option<Account<'info, RelayerEntry>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: instructions sysvar, read for the Ed25519 attestation check.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeProposalRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub request: Account<'info, ProposalRequest>,
+    #[account(
+        init,
+        payer = disputer,
+        space = Dispute::space(),
+        seeds = [b"dispute", request.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub request: Account<'info, ProposalRequest>,
+    #[account(
+        mut,
+        close = disputer,
+        seeds = [b"dispute", request.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    /// CHECK: must equal dispute.disputer; gets the bond back when overturned.
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: UncheckedAccount<'info>,
+    /// CHECK: must equal request.relayer; receives the fee and slashed bond when upheld.
+    #[account(mut, address = request.relayer @ ErrorCode::BadRelayer)]
+    pub relayer: UncheckedAccount<'info>,
+    /// CHECK: must equal request.authority; gets the fee refunded when overturned.
+    #[account(mut, address = request.authority @ ErrorCode::NotRequestAuthority)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"relayer", resolver.key().as_ref()],
+        bump = relayer_entry.bump
+    )]
+    pub relayer_entry: Option<Account<'info, RelayerEntry>>,
+    pub resolver: Signer<'info>,
+
+    /// CHECK: instructions sysvar, read for the Ed25519 attestation check on relayer re-runs.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut, close = authority)]
+    pub request: Account<'info, ProposalRequest>,
+    #[account(
+        mut,
+        close = disputer,
+        seeds = [b"dispute", request.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    /// CHECK: must equal request.authority; receives the escrowed fee and the request rent.
+    #[account(mut, address = request.authority @ ErrorCode::NotRequestAuthority)]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: must equal dispute.disputer; receives the bond back.
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposalRequest<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub request: Account<'info, ProposalRequest>,
+    /// CHECK: must equal request.relayer; receives the escrowed fee.
+    #[account(mut, address = request.relayer @ ErrorCode::BadRelayer)]
+    pub relayer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelJudgeRequest<'info> {
+    #[account(mut, close = authority)]
+    pub request: Account<'info, JudgeRequest>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposalRequest<'info> {
+    #[account(mut, close = authority)]
+    pub request: Account<'info, ProposalRequest>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyReceiptLeaf<'info> {
+    /// CHECK: JudgeRequest, ProposalRequest or JudgeResult; deserialized in the handler.
+    pub receipt_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Treasury::space(),
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxActionLamports<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundTreasury<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitJudgeRegistry<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = JudgeRegistry::space(),
+        seeds = [b"judge_registry"],
+        bump
+    )]
+    pub judge_registry: Account<'info, JudgeRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateJudgeRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"judge_registry"],
+        bump = judge_registry.bump
+    )]
+    pub judge_registry: Account<'info, JudgeRegistry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(judge: Pubkey)]
+pub struct AddJudge<'info> {
+    #[account(
+        seeds = [b"judge_registry"],
+        bump = judge_registry.bump
+    )]
+    pub judge_registry: Account<'info, JudgeRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = JudgeEntry::space(),
+        seeds = [b"judge_entry", judge.as_ref()],
+        bump
+    )]
+    pub judge_entry: Account<'info, JudgeEntry>,
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateJudge<'info> {
+    #[account(
+        seeds = [b"judge_registry"],
+        bump = judge_registry.bump
+    )]
+    pub judge_registry: Account<'info, JudgeRegistry>,
+    #[account(
+        mut,
+        seeds = [b"judge_entry", judge_entry.judge.as_ref()],
+        bump = judge_entry.bump
+    )]
+    pub judge_entry: Account<'info, JudgeEntry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveJudge<'info> {
+    #[account(
+        seeds = [b"judge_registry"],
+        bump = judge_registry.bump
+    )]
+    pub judge_registry: Account<'info, JudgeRegistry>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"judge_entry", judge_entry.judge.as_ref()],
+        bump = judge_entry.bump
+    )]
+    pub judge_entry: Account<'info, JudgeEntry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitGovernanceConfig<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = GovernanceConfig::space(),
+        seeds = [b"governance_config"],
+        bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernanceConfig<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositGoverningTokens<'info> {
+    pub governing_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = VoterDeposit::space(),
+        seeds = [b"voter_deposit", governing_mint.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"deposit_vault", governing_mint.key().as_ref()],
+        bump,
+        token::mint = governing_mint,
+        token::authority = deposit_vault
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = governing_mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawGoverningTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"voter_deposit", voter_deposit.mint.as_ref(), owner.key().as_ref()],
+        bump = voter_deposit.bump
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+    #[account(
+        mut,
+        seeds = [b"deposit_vault", voter_deposit.mint.as_ref()],
+        bump
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = voter_deposit.mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct Delegate<'info> {
+    #[account(
+        init,
+        payer = delegator,
+        space = Delegation::space(),
+        seeds = [b"delegation", mint.as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [b"delegation", delegation.mint.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_text: String, revision_number: u64, nonce: u64)]
+pub struct CreateGovernanceProposal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"governance_config"],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = user,
+        space = Proposal::space(),
+        seeds = [b"proposal_v2", user.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = user,
+        space = ProposalRevision::space(),
+        seeds = [b"revision", proposal.key().as_ref(), &revision_number.to_le_bytes()],
+        bump
+    )]
+    pub revision: Account<'info, ProposalRevision>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(revision_number: u64, revision_text: String)]
+pub struct AddRevision<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
     #[account(
         init,
-        payer = admin,
-        space = 8 + 32 + 32,
-        seeds = [b"config"],
+        payer = user,
+        space = ProposalRevision::space(),
+        seeds = [b"revision", proposal.key().as_ref(), &revision_number.to_le_bytes()],
         bump
     )]
-    pub config: Account<'info, Config>,
+    pub revision: Account<'info, ProposalRevision>,
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(criteria: String, input_a: String, input_b: String, nonce: u64)]
-pub struct CreateJudgeRequest<'info> {
+pub struct CastVote<'info> {
     #[account(
         seeds = [b"config"],
         bump
     )]
     pub config: Account<'info, Config>,
-
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
     #[account(
         init,
-        payer = user,
-        space = JudgeRequest::space(),
-        seeds = [b"req", user.key().as_ref(), &nonce.to_le_bytes()],
+        payer = voter,
+        space = VoteRecord::space(),
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
         bump
     )]
-    pub request: Account<'info, JudgeRequest>,
-
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(
+        mut,
+        seeds = [b"voter_deposit", proposal.governing_mint.as_ref(), voter.key().as_ref()],
+        bump = voter_deposit.bump
+    )]
+    pub voter_deposit: Option<Account<'info, VoterDeposit>>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub voter: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FulfillJudgeRequest<'info> {
+pub struct ChangeVote<'info> {
     #[account(
         seeds = [b"config"],
         bump
     )]
     pub config: Account<'info, Config>,
-
     #[account(mut)]
-    pub request: Account<'info, JudgeRequest>,
-
-    pub relayer: Signer<'info>,
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    pub voter: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(source: String, proposal_id: String, proposal_text: String, nonce: u64)]
-pub struct CreateProposalRequest<'info> {
+pub struct WithdrawVote<'info> {
     #[account(
         seeds = [b"config"],
         bump
     )]
     pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct SubmitJudgeResult<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"judge_entry", judge.key().as_ref()],
+        bump = judge_entry.bump
+    )]
+    pub judge_entry: Account<'info, JudgeEntry>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
     #[account(
         init,
-        payer = user,
-        space = ProposalRequest::space(),
-        seeds = [b"proposal", user.key().as_ref(), &nonce.to_le_bytes()],
+        payer = payer,
+        space = JudgeResult::space(),
+        seeds = [b"judge", proposal.key().as_ref(), judge.key().as_ref()],
         bump
     )]
-    pub request: Account<'info, ProposalRequest>,
-
+    pub judge_result: Account<'info, JudgeResult>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub payer: Signer<'info>,
+    pub judge: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FulfillProposalRequest<'info> {
+pub struct RevealJudgeResult<'info> {
     #[account(
         seeds = [b"config"],
         bump
     )]
     pub config: Account<'info, Config>,
-
     #[account(mut)]
-    pub request: Account<'info, ProposalRequest>,
-
-    pub relayer: Signer<'info>,
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"judge", proposal.key().as_ref(), judge.key().as_ref()],
+        bump
+    )]
+    pub judge_result: Account<'info, JudgeResult>,
+    pub judge: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitTreasury<'info> {
+pub struct FinalizeConsensus<'info> {
     #[account(
-        init,
-        payer = payer,
-        space = Treasury::space(),
-        seeds = [b"treasury"],
+        seeds = [b"config"],
         bump
     )]
-    pub treasury: Account<'info, Treasury>,
+    pub config: Account<'info, Config>,
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub finalizer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FundTreasury<'info> {
+#[instruction(index: u8)]
+pub struct CompleteAction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"action", proposal.key().as_ref(), &[index]],
+        bump
+    )]
+    pub action_request: Account<'info, ActionRequest>,
     #[account(
         seeds = [b"treasury"],
         bump = treasury.bump
@@ -479,130 +3172,191 @@ pub struct FundTreasury<'info> {
     )]
     pub treasury_vault: SystemAccount<'info>,
     #[account(mut)]
-    pub funder: Signer<'info>,
+    pub recipient: SystemAccount<'info>,
+    #[account(mut)]
+    pub executor: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(proposal_text: String, revision_number: u64, nonce: u64)]
-pub struct CreateGovernanceProposal<'info> {
+pub struct CompleteAllActions<'info> {
     #[account(
-        init,
-        payer = user,
-        space = Proposal::space(),
-        seeds = [b"proposal_v2", user.key().as_ref(), &nonce.to_le_bytes()],
+        seeds = [b"config"],
         bump
     )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     #[account(
-        init,
-        payer = user,
-        space = ProposalRevision::space(),
-        seeds = [b"revision", proposal.key().as_ref(), &revision_number.to_le_bytes()],
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
         bump
     )]
-    pub revision: Account<'info, ProposalRevision>,
+    pub treasury_vault: SystemAccount<'info>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub executor: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(revision_number: u64, revision_text: String)]
-pub struct AddRevision<'info> {
+#[instruction(index: u8)]
+pub struct InsertProposalInstruction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     #[account(
         init,
-        payer = user,
-        space = ProposalRevision::space(),
-        seeds = [b"revision", proposal.key().as_ref(), &revision_number.to_le_bytes()],
+        payer = authority,
+        space = ProposalInstruction::space(),
+        seeds = [b"proposal_ix", proposal.key().as_ref(), &[index]],
         bump
     )]
-    pub revision: Account<'info, ProposalRevision>,
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CastVote<'info> {
+#[instruction(index: u8)]
+pub struct ExecuteInstruction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     #[account(
-        init,
-        payer = voter,
-        space = VoteRecord::space(),
-        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        mut,
+        seeds = [b"proposal_ix", proposal.key().as_ref(), &[index]],
         bump
     )]
-    pub vote_record: Account<'info, VoteRecord>,
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseJudgeRequest<'info> {
+    #[account(mut, close = authority)]
+    pub request: Account<'info, JudgeRequest>,
     #[account(mut)]
-    pub voter: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitJudgeResult<'info> {
+pub struct CloseRelayerResult<'info> {
+    /// CHECK: the JudgeRequest the result belongs to; may already be closed.
+    pub request: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [b"relayer_result", request.key().as_ref(), relayer.key().as_ref()],
+        bump = relayer_result.bump
+    )]
+    pub relayer_result: Account<'info, RelayerResult>,
     #[account(mut)]
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposalRequest<'info> {
+    #[account(mut, close = authority)]
+    pub request: Account<'info, ProposalRequest>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRevision<'info> {
     pub proposal: Account<'info, Proposal>,
     #[account(
-        init,
-        payer = payer,
-        space = JudgeResult::space(),
-        seeds = [b"judge", proposal.key().as_ref(), judge.key().as_ref()],
+        mut,
+        close = authority,
+        seeds = [b"revision", proposal.key().as_ref(), &revision.revision_number.to_le_bytes()],
         bump
     )]
-    pub judge_result: Account<'info, JudgeResult>,
+    pub revision: Account<'info, ProposalRevision>,
     #[account(mut)]
-    pub payer: Signer<'info>,
-    pub judge: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct FinalizeConsensus<'info> {
-    #[account(mut)]
+pub struct CloseVoteRecord<'info> {
     pub proposal: Account<'info, Proposal>,
     #[account(
-        init,
-        payer = finalizer,
-        space = ActionRequest::space(),
-        seeds = [b"action", proposal.key().as_ref()],
+        mut,
+        close = receiver,
+        seeds = [b"vote", proposal.key().as_ref(), vote_record.voter.as_ref()],
         bump
     )]
-    pub action_request: Account<'info, ActionRequest>,
+    pub vote_record: Account<'info, VoteRecord>,
     #[account(mut)]
-    pub finalizer: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub receiver: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct CompleteAction<'info> {
-    #[account(mut)]
+pub struct CloseJudgeResult<'info> {
     pub proposal: Account<'info, Proposal>,
     #[account(
         mut,
-        seeds = [b"action", proposal.key().as_ref()],
+        close = receiver,
+        seeds = [b"judge", proposal.key().as_ref(), judge_result.judge.as_ref()],
         bump
     )]
-    pub action_request: Account<'info, ActionRequest>,
+    pub judge_result: Account<'info, JudgeResult>,
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct CloseActionRequest<'info> {
+    pub proposal: Account<'info, Proposal>,
     #[account(
-        seeds = [b"treasury"],
-        bump = treasury.bump
+        mut,
+        close = receiver,
+        seeds = [b"action", proposal.key().as_ref(), &[index]],
+        bump
     )]
-    pub treasury: Account<'info, Treasury>,
+    pub action_request: Account<'info, ActionRequest>,
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct CloseProposalInstruction<'info> {
+    pub proposal: Account<'info, Proposal>,
     #[account(
         mut,
-        seeds = [b"treasury_vault"],
+        close = authority,
+        seeds = [b"proposal_ix", proposal.key().as_ref(), &[index]],
         bump
     )]
-    pub treasury_vault: SystemAccount<'info>,
-    #[account(mut)]
-    pub recipient: SystemAccount<'info>,
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
     #[account(mut)]
-    pub executor: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -620,9 +3374,45 @@ pub struct InitTreasuryVault<'info> {
 }
 
 #[account]
-pub struct Config {
-    pub admin: Pubkey,
+pub struct Config {
+    pub admin: Pubkey,
+    pub relayer: Pubkey,
+    pub pending_admin: Pubkey,
+    pub paused: bool,
+    pub pause_flags: u8,
+    pub attestors: Vec<Pubkey>,
+    pub min_request_fee: u64,
+    pub challenge_period_secs: i64,
+    pub dispute_bond_lamports: u64,
+    pub dispute_resolution_secs: i64,
+}
+
+impl Config {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 32
+        + 32
+        + 1
+        + 1
+        + 4 + 32 * MAX_ATTESTORS
+        + 8
+        + 8
+        + 8
+        + 8
+    }
+}
+
+#[account]
+pub struct RelayerEntry {
     pub relayer: Pubkey,
+    pub bump: u8,
+}
+
+impl RelayerEntry {
+    pub fn space() -> usize {
+        8 + 32 + 1
+    }
 }
 
 #[account]
@@ -635,6 +3425,20 @@ pub struct JudgeRequest {
 
     pub response_hash: [u8; 32],
     pub receipt_root: [u8; 32],
+    pub relayer: Pubkey,
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub fee_lamports: u64,
+    pub relayer_quorum: u8,
+    pub relayer_panel: u8,
+    pub match_response_hash: bool,
+    pub submissions: u8,
+    pub tallies: Vec<RelayerTally>,
+    pub callback_program: Pubkey,
+    pub callback_accounts: Vec<CallbackAccount>,
+    pub callback_delivered: bool,
 
     pub criteria: String,
     pub input_a: String,
@@ -650,10 +3454,139 @@ impl JudgeRequest {
         + 1
         + 32
         + 32
+        + 32
+        + 32
+        + 4 + MAX_MODEL_ID_LEN
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 1
+        + 1
+        + 4 + RelayerTally::space() * MAX_RELAYER_PANEL as usize
+        + 32
+        + 4 + CallbackAccount::space() * MAX_CALLBACK_ACCOUNTS
+        + 1
         + 4 + MAX_CRITERIA_LEN
         + 4 + MAX_INPUT_LEN
         + 4 + MAX_INPUT_LEN
     }
+
+    pub fn tally(&mut self, decision: u8, response_hash: [u8; 32]) -> u8 {
+        let key = if self.match_response_hash {
+            response_hash
+        } else {
+            [0u8; 32]
+        };
+        match self
+            .tallies
+            .iter_mut()
+            .find(|t| t.decision == decision && t.response_hash == key)
+        {
+            Some(tally) => {
+                tally.count += 1;
+                tally.count
+            }
+            None => {
+                self.tallies.push(RelayerTally {
+                    decision,
+                    response_hash: key,
+                    count: 1,
+                });
+                1
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayerQuorumParams {
+    pub relayer_quorum: u8,
+    pub relayer_panel: u8,
+    pub match_response_hash: bool,
+}
+
+impl RelayerQuorumParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.relayer_quorum <= 1
+                || (self.relayer_quorum <= self.relayer_panel
+                    && self.relayer_panel <= MAX_RELAYER_PANEL),
+            ErrorCode::BadRelayerQuorum
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CallbackParams {
+    pub program_id: Pubkey,
+    pub accounts: Vec<CallbackAccount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ActionParams {
+    pub amount_lamports: u64,
+    pub recipient: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InstructionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CallbackAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+impl CallbackAccount {
+    pub fn space() -> usize {
+        32 + 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayerTally {
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub count: u8,
+}
+
+impl RelayerTally {
+    pub fn space() -> usize {
+        1 + 32 + 1
+    }
+}
+
+#[account]
+pub struct RelayerResult {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+    pub bump: u8,
+}
+
+impl RelayerResult {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 32
+        + 1
+        + 32
+        + 32
+        + 32
+        + 4 + MAX_MODEL_ID_LEN
+        + 1
+    }
 }
 
 #[account]
@@ -668,6 +3601,11 @@ pub struct ProposalRequest {
     pub receipt_root: [u8; 32],
     pub prompt_hash: [u8; 32],
     pub model_id: String,
+    pub relayer: Pubkey,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub fee_lamports: u64,
+    pub challenge_ends_at: i64,
 
     pub source: String,
     pub proposal_id: String,
@@ -685,20 +3623,187 @@ impl ProposalRequest {
         + 32
         + 32
         + 4 + MAX_MODEL_ID_LEN
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
         + 4 + MAX_SOURCE_LEN
         + 4 + MAX_PROPOSAL_ID_LEN
         + 4 + MAX_PROPOSAL_TEXT_LEN
     }
 }
 
+#[account]
+pub struct Dispute {
+    pub request: Pubkey,
+    pub disputer: Pubkey,
+    pub bond_lamports: u64,
+    pub bump: u8,
+    pub resolve_ends_at: i64,
+}
+
+impl Dispute {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 32
+        + 8
+        + 1
+        + 8
+    }
+}
+
 #[account]
 pub struct Treasury {
     pub bump: u8,
+    pub max_action_lamports: u64,
 }
 
 impl Treasury {
     pub fn space() -> usize {
-        8 + 1
+        8 + 1 + 8
+    }
+}
+
+#[account]
+pub struct JudgeRegistry {
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl JudgeRegistry {
+    pub fn space() -> usize {
+        8 + 32 + 1
+    }
+}
+
+#[account]
+pub struct JudgeEntry {
+    pub judge: Pubkey,
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl JudgeEntry {
+    pub fn space() -> usize {
+        8 + 32 + 1 + 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GovernanceParams {
+    pub judge_panel_size: u8,
+    pub consensus_mode: u8,
+    pub voting_period_secs: i64,
+    pub judging_period_secs: i64,
+    pub decision_policy: u8,
+    pub quorum_votes: u64,
+    pub approval_ratio_bps: u16,
+    pub judge_weight_bps: u16,
+    pub governing_mint: Pubkey,
+    pub secret_ballot: bool,
+    pub reveal_period_secs: i64,
+    pub judge_reveal_period_secs: i64,
+}
+
+impl GovernanceParams {
+    pub fn space() -> usize {
+        1 + 1 + 8 + 8 + 1 + 8 + 2 + 2 + 32 + 1 + 8 + 8
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.judge_panel_size >= 1 && self.judge_panel_size <= MAX_JUDGE_PANEL_SIZE,
+            ErrorCode::BadGovernanceParams
+        );
+        require!(
+            (CONSENSUS_MAJORITY..=CONSENSUS_UNANIMITY).contains(&self.consensus_mode),
+            ErrorCode::BadGovernanceParams
+        );
+        require!(
+            self.voting_period_secs > 0
+                && self.judging_period_secs > 0
+                && self.judge_reveal_period_secs > 0,
+            ErrorCode::BadGovernanceParams
+        );
+        require!(
+            (POLICY_JUDGES_ONLY..=POLICY_WEIGHTED).contains(&self.decision_policy),
+            ErrorCode::BadGovernanceParams
+        );
+        require!(
+            self.approval_ratio_bps as u128 <= BPS_DENOMINATOR
+                && self.judge_weight_bps as u128 <= BPS_DENOMINATOR,
+            ErrorCode::BadGovernanceParams
+        );
+        require!(
+            !self.secret_ballot || self.reveal_period_secs > 0,
+            ErrorCode::BadGovernanceParams
+        );
+        Ok(())
+    }
+
+    pub fn judge_threshold(&self) -> u8 {
+        let n = self.judge_panel_size;
+        match self.consensus_mode {
+            CONSENSUS_SUPERMAJORITY => (2 * n).div_ceil(3),
+            CONSENSUS_UNANIMITY => n,
+            _ => n / 2 + 1,
+        }
+    }
+}
+
+#[account]
+pub struct GovernanceConfig {
+    pub bump: u8,
+    pub params: GovernanceParams,
+}
+
+impl GovernanceConfig {
+    pub fn space() -> usize {
+        8 + 1 + GovernanceParams::space()
+    }
+}
+
+#[account]
+pub struct VoterDeposit {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub locked_until: i64,
+    pub bump: u8,
+}
+
+impl VoterDeposit {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+    }
+}
+
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub mint: Pubkey,
+    pub scope: Pubkey,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 8
+        + 1
     }
 }
 
@@ -715,24 +3820,165 @@ pub struct Proposal {
     pub judge_reject: u8,
     pub judge_needs: u8,
     pub final_verdict: u8,
+    pub judge_panel_size: u8,
+    pub judge_threshold: u8,
+    pub voting_starts_at: i64,
+    pub voting_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub judging_ends_at: i64,
+    pub decision_policy: u8,
+    pub quorum_votes: u64,
+    pub approval_ratio_bps: u16,
+    pub judge_weight_bps: u16,
+    pub judge_verdict: u8,
+    pub vote_verdict: u8,
+    pub governing_mint: Pubkey,
+    pub secret_ballot: bool,
+    pub unrevealed_votes: u64,
+    pub unrevealed_weight: u64,
+    pub judge_reveal_ends_at: i64,
+    pub judge_commits: u8,
+    pub unrevealed_judges: u8,
+    pub atomic_actions: bool,
+    pub actions: Vec<ActionParams>,
+    pub instruction_count: u8,
+    pub spent_lamports: u64,
     pub proposal_text: String,
 }
 
-impl Proposal {
-    pub fn space() -> usize {
-        8
-        + 32
-        + 1
-        + 8
-        + 8
-        + 8
-        + 8
-        + 8
-        + 1
-        + 1
-        + 1
-        + 1
-        + 4 + MAX_GOV_PROPOSAL_TEXT_LEN
+impl Proposal {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 1
+        + 1
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 2
+        + 2
+        + 1
+        + 1
+        + 32
+        + 1
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 1
+        + 4 + MAX_PROPOSAL_ACTIONS * (8 + 32)
+        + 1
+        + 8
+        + 4 + MAX_GOV_PROPOSAL_TEXT_LEN
+    }
+
+    pub fn voting_open(&self, now: i64) -> bool {
+        now >= self.voting_starts_at && now < self.voting_ends_at
+    }
+
+    pub fn add_votes(&mut self, choice: u8, weight: u64) {
+        match choice {
+            1 => self.votes_for = self.votes_for.checked_add(weight).unwrap(),
+            2 => self.votes_against = self.votes_against.checked_add(weight).unwrap(),
+            3 => self.votes_abstain = self.votes_abstain.checked_add(weight).unwrap(),
+            _ => {}
+        }
+    }
+
+    pub fn remove_votes(&mut self, choice: u8, weight: u64) {
+        match choice {
+            1 => self.votes_for = self.votes_for.checked_sub(weight).unwrap(),
+            2 => self.votes_against = self.votes_against.checked_sub(weight).unwrap(),
+            3 => self.votes_abstain = self.votes_abstain.checked_sub(weight).unwrap(),
+            _ => {}
+        }
+    }
+
+    pub fn judges_revealed(&self) -> u16 {
+        self.judge_approve as u16 + self.judge_reject as u16 + self.judge_needs as u16
+    }
+
+    pub fn judge_reveal_open(&self, now: i64) -> bool {
+        now >= self.judging_ends_at || self.judge_commits >= self.judge_panel_size
+    }
+
+    pub fn has_votes(&self) -> bool {
+        self.votes_for > 0
+            || self.votes_against > 0
+            || self.votes_abstain > 0
+            || self.unrevealed_votes > 0
+    }
+
+    pub fn record_spend(&mut self, amount: u64, cap: u64) -> Result<()> {
+        self.spent_lamports = self.spent_lamports.checked_add(amount).unwrap();
+        require!(self.spent_lamports <= cap, ErrorCode::ActionOverCap);
+        Ok(())
+    }
+
+    pub fn judge_outcome(&self) -> u8 {
+        if self.judge_approve >= self.judge_threshold {
+            1
+        } else if self.judge_reject >= self.judge_threshold {
+            2
+        } else {
+            3
+        }
+    }
+
+    pub fn quorum_reached(&self) -> bool {
+        let total = self.votes_for as u128
+            + self.votes_against as u128
+            + self.votes_abstain as u128;
+        total >= self.quorum_votes as u128 && total > 0
+    }
+
+    pub fn vote_approval_bps(&self) -> u128 {
+        let decisive = self.votes_for as u128 + self.votes_against as u128;
+        if decisive == 0 {
+            return 0;
+        }
+        self.votes_for as u128 * BPS_DENOMINATOR / decisive
+    }
+
+    pub fn vote_outcome(&self) -> u8 {
+        if !self.quorum_reached() {
+            3
+        } else if self.vote_approval_bps() > self.approval_ratio_bps as u128 {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn weighted_outcome(&self) -> u8 {
+        if !self.quorum_reached() || self.judge_panel_size == 0 {
+            return 3;
+        }
+        let judge_bps =
+            self.judge_approve as u128 * BPS_DENOMINATOR / self.judge_panel_size as u128;
+        let judge_weight = self.judge_weight_bps as u128;
+        let blended = (judge_bps * judge_weight
+            + self.vote_approval_bps() * (BPS_DENOMINATOR - judge_weight))
+            / BPS_DENOMINATOR;
+        if blended > self.approval_ratio_bps as u128 {
+            1
+        } else {
+            2
+        }
     }
 }
 
@@ -757,6 +4003,11 @@ pub struct VoteRecord {
     pub proposal: Pubkey,
     pub voter: Pubkey,
     pub choice: u8,
+    pub weight: u64,
+    pub delegate: Pubkey,
+    pub delegated_weight: u64,
+    pub delegators: Vec<Pubkey>,
+    pub commitment: [u8; 32],
 }
 
 impl VoteRecord {
@@ -765,6 +4016,11 @@ impl VoteRecord {
         + 32
         + 32
         + 1
+        + 8
+        + 32
+        + 8
+        + 4 + 32 * MAX_DELEGATORS_PER_VOTE
+        + 32
     }
 }
 
@@ -773,6 +4029,13 @@ pub struct JudgeResult {
     pub proposal: Pubkey,
     pub judge: Pubkey,
     pub verdict: u8,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+    pub rationale_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub payer: Pubkey,
 }
 
 impl JudgeResult {
@@ -781,6 +4044,13 @@ impl JudgeResult {
         + 32
         + 32
         + 1
+        + 32
+        + 1
+        + 32
+        + 4 + MAX_MODEL_ID_LEN
+        + 32
+        + 32
+        + 32
     }
 }
 
@@ -791,6 +4061,8 @@ pub struct ActionRequest {
     pub amount_lamports: u64,
     pub recipient: Pubkey,
     pub executor: Pubkey,
+    pub index: u8,
+    pub payer: Pubkey,
 }
 
 impl ActionRequest {
@@ -801,9 +4073,335 @@ impl ActionRequest {
         + 8
         + 32
         + 32
+        + 1
+        + 32
+    }
+}
+
+#[account]
+pub struct ProposalInstruction {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub executed: bool,
+    pub executor: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<InstructionAccount>,
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    pub fn space() -> usize {
+        8
+        + 32
+        + 1
+        + 1
+        + 32
+        + 32
+        + 4 + MAX_INSTRUCTION_ACCOUNTS * (32 + 1 + 1)
+        + 4 + MAX_INSTRUCTION_DATA_LEN
     }
 }
 
+#[event]
+pub struct JudgeRequestCreated {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+    pub nonce: u64,
+    pub criteria_hash: [u8; 32],
+    pub fee_lamports: u64,
+    pub relayer_quorum: u8,
+    pub relayer_panel: u8,
+    pub callback_program: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct RelayerResultSubmitted {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub agreeing: u8,
+    pub status: u8,
+}
+
+#[event]
+pub struct JudgeRequestFulfilled {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+}
+
+#[event]
+pub struct CallbackDelivered {
+    pub request: Pubkey,
+    pub callback_program: Pubkey,
+}
+
+#[event]
+pub struct ProposalRequestCreated {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+    pub nonce: u64,
+    pub source: String,
+    pub proposal_id: String,
+    pub text_hash: [u8; 32],
+    pub fee_lamports: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ProposalRequestFulfilled {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub verdict_code: u8,
+    pub summary_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+    pub challenge_ends_at: i64,
+}
+
+#[event]
+pub struct ProposalRequestDisputed {
+    pub request: Pubkey,
+    pub disputer: Pubkey,
+    pub bond_lamports: u64,
+    pub resolve_ends_at: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub request: Pubkey,
+    pub resolver: Pubkey,
+    pub upheld: bool,
+    pub verdict_code: u8,
+    pub summary_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+}
+
+#[event]
+pub struct DisputeExpired {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+    pub disputer: Pubkey,
+    pub fee_lamports: u64,
+    pub bond_lamports: u64,
+}
+
+#[event]
+pub struct ProposalRequestFinalized {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub verdict_code: u8,
+    pub summary_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+}
+
+#[event]
+pub struct RequestCancelled {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct TreasuryFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub authority: Pubkey,
+    pub nonce: u64,
+    pub text_hash: [u8; 32],
+    pub actions: Vec<ActionParams>,
+    pub atomic_actions: bool,
+    pub decision_policy: u8,
+    pub judge_panel_size: u8,
+    pub judge_threshold: u8,
+    pub governing_mint: Pubkey,
+    pub secret_ballot: bool,
+    pub voting_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub judging_ends_at: i64,
+    pub judge_reveal_ends_at: i64,
+}
+
+#[event]
+pub struct RevisionAdded {
+    pub proposal: Pubkey,
+    pub revision_number: u64,
+    pub text_hash: [u8; 32],
+    pub actions: Vec<ActionParams>,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub choice: u8,
+    pub weight: u64,
+    pub delegated_weight: u64,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct VoteWithdrawn {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub choice: u8,
+    pub weight: u64,
+    pub delegated_weight: u64,
+}
+
+#[event]
+pub struct JudgeResultSubmitted {
+    pub proposal: Pubkey,
+    pub judge: Pubkey,
+    pub commitment: [u8; 32],
+    pub prompt_hash: [u8; 32],
+    pub model_id: String,
+    pub rationale_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+}
+
+#[event]
+pub struct JudgeResultRevealed {
+    pub proposal: Pubkey,
+    pub judge: Pubkey,
+    pub verdict: u8,
+}
+
+#[event]
+pub struct ConsensusFinalized {
+    pub proposal: Pubkey,
+    pub final_verdict: u8,
+    pub judge_verdict: u8,
+    pub vote_verdict: u8,
+    pub judge_approve: u8,
+    pub judge_reject: u8,
+    pub judge_needs: u8,
+    pub unrevealed_judges: u8,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub unrevealed_votes: u64,
+    pub action_count: u8,
+    pub action_status: u8,
+}
+
+#[event]
+pub struct ActionCompleted {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub recipient: Pubkey,
+    pub amount_lamports: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ProposalInstructionInserted {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub program_id: Pubkey,
+    pub account_count: u8,
+    pub data_hash: [u8; 32],
+}
+
+#[event]
+pub struct ProposalInstructionExecuted {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub program_id: Pubkey,
+    pub executor: Pubkey,
+    pub vault_spent_lamports: u64,
+}
+
+#[event]
+pub struct JudgeRequestClosed {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub prompt_hash: [u8; 32],
+}
+
+#[event]
+pub struct RelayerResultClosed {
+    pub request: Pubkey,
+    pub relayer: Pubkey,
+    pub decision: u8,
+    pub response_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+}
+
+#[event]
+pub struct ProposalRequestClosed {
+    pub request: Pubkey,
+    pub authority: Pubkey,
+    pub verdict_code: u8,
+    pub summary_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+    pub prompt_hash: [u8; 32],
+}
+
+#[event]
+pub struct ProposalRevisionClosed {
+    pub proposal: Pubkey,
+    pub revision_number: u64,
+    pub text_hash: [u8; 32],
+}
+
+#[event]
+pub struct VoteRecordClosed {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub choice: u8,
+    pub weight: u64,
+    pub delegated_weight: u64,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct JudgeResultClosed {
+    pub proposal: Pubkey,
+    pub judge: Pubkey,
+    pub verdict: u8,
+    pub revealed: bool,
+    pub commitment: [u8; 32],
+    pub prompt_hash: [u8; 32],
+    pub rationale_hash: [u8; 32],
+    pub receipt_root: [u8; 32],
+}
+
+#[event]
+pub struct ActionRequestClosed {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub status: u8,
+    pub amount_lamports: u64,
+    pub recipient: Pubkey,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ProposalInstructionClosed {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub executed: bool,
+    pub program_id: Pubkey,
+    pub data_hash: [u8; 32],
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Criteria too long")]
@@ -848,4 +4446,294 @@ pub enum ErrorCode {
     BadRecipient,
     #[msg("Not authority")]
     NotAuthority,
+    #[msg("Not admin")]
+    NotAdmin,
+    #[msg("No pending admin")]
+    NoPendingAdmin,
+    #[msg("Not pending admin")]
+    NotPendingAdmin,
+    #[msg("Program is paused")]
+    Paused,
+    #[msg("Not judge registry authority")]
+    NotRegistryAuthority,
+    #[msg("Judge not active")]
+    JudgeNotActive,
+    #[msg("Bad governance params")]
+    BadGovernanceParams,
+    #[msg("Voting is closed")]
+    VotingClosed,
+    #[msg("Voting has not ended")]
+    VotingNotEnded,
+    #[msg("Judging is closed")]
+    JudgingClosed,
+    #[msg("Bad amount")]
+    BadAmount,
+    #[msg("Deposit is locked by an active vote")]
+    DepositLocked,
+    #[msg("Insufficient deposit")]
+    InsufficientDeposit,
+    #[msg("No voting weight")]
+    NoVotingWeight,
+    #[msg("Bad delegation")]
+    BadDelegation,
+    #[msg("Delegation expired")]
+    DelegationExpired,
+    #[msg("Bad delegation accounts")]
+    BadDelegationAccounts,
+    #[msg("Too many delegators")]
+    TooManyDelegators,
+    #[msg("Already voted")]
+    AlreadyVoted,
+    #[msg("Vote was cast by a delegate")]
+    DelegatedVote,
+    #[msg("Proposal uses secret ballots")]
+    SecretBallot,
+    #[msg("Proposal does not use secret ballots")]
+    NotSecretBallot,
+    #[msg("Bad commitment")]
+    BadCommitment,
+    #[msg("Reveal window is closed")]
+    RevealClosed,
+    #[msg("Already revealed")]
+    AlreadyRevealed,
+    #[msg("Commitment mismatch")]
+    CommitmentMismatch,
+    #[msg("Reveal window is not open")]
+    RevealNotOpen,
+    #[msg("Attestor already registered")]
+    AttestorExists,
+    #[msg("Too many attestors")]
+    TooManyAttestors,
+    #[msg("Unknown attestor")]
+    UnknownAttestor,
+    #[msg("Missing Ed25519 attestation instruction")]
+    MissingAttestation,
+    #[msg("Attestation does not match fulfillment")]
+    BadAttestation,
+    #[msg("Receipt proof too long")]
+    ReceiptProofTooLong,
+    #[msg("Account has no receipt root")]
+    NoReceiptRoot,
+    #[msg("Receipt proof does not match receipt root")]
+    ReceiptProofMismatch,
+    #[msg("Request expired")]
+    RequestExpired,
+    #[msg("Only the request authority can do this")]
+    NotRequestAuthority,
+    #[msg("Request fee below config minimum")]
+    FeeTooLow,
+    #[msg("Bad dispute params")]
+    BadDisputeParams,
+    #[msg("Request result is not in the proposed state")]
+    NotProposed,
+    #[msg("Request result is not disputed")]
+    NotDisputed,
+    #[msg("Challenge window is closed")]
+    ChallengeClosed,
+    #[msg("Challenge window is still open")]
+    ChallengeOpen,
+    #[msg("Signer cannot resolve this dispute")]
+    NotDisputeResolver,
+    #[msg("Bad relayer quorum")]
+    BadRelayerQuorum,
+    #[msg("Request requires relayer quorum submissions")]
+    RelayerQuorumRequired,
+    #[msg("Request does not use relayer quorum")]
+    RelayerQuorumNotEnabled,
+    #[msg("Too many callback accounts")]
+    TooManyCallbackAccounts,
+    #[msg("Callback accounts do not match the request")]
+    BadCallbackAccounts,
+    #[msg("Account is not in a closable state")]
+    NotClosable,
+    #[msg("Action amount exceeds treasury cap")]
+    ActionOverCap,
+    #[msg("Too many actions")]
+    TooManyActions,
+    #[msg("Bad action accounts")]
+    BadActionAccounts,
+    #[msg("Proposal actions must be completed together")]
+    AtomicActions,
+    #[msg("Bad instruction index")]
+    BadInstructionIndex,
+    #[msg("Too many instructions")]
+    TooManyInstructions,
+    #[msg("Too many instruction accounts")]
+    TooManyInstructionAccounts,
+    #[msg("Instruction data too long")]
+    InstructionDataTooLong,
+    #[msg("Only the treasury vault may sign proposal instructions")]
+    BadInstructionSigner,
+    #[msg("Bad instruction accounts")]
+    BadInstructionAccounts,
+    #[msg("Instruction already executed")]
+    InstructionExecuted,
+    #[msg("Proposal instruction changed the treasury vault's owner or data")]
+    TreasuryVaultModified,
+    #[msg("Votes have already been cast on this proposal")]
+    VotesAlreadyCast,
+    #[msg("Receipt leaf index does not fit the proof depth")]
+    ReceiptLeafIndexOutOfRange,
+    #[msg("Request is not fulfilled")]
+    NotFulfilled,
+    #[msg("Request has no callback")]
+    NoCallback,
+    #[msg("Callback already delivered")]
+    CallbackDelivered,
+    #[msg("Dispute resolution deadline has passed")]
+    DisputeResolutionClosed,
+    #[msg("Dispute resolution deadline has not passed")]
+    DisputeResolutionOpen,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf_hash(leaf: &[u8; 32]) -> [u8; 32] {
+        hashv(&[&[RECEIPT_LEAF_PREFIX], leaf.as_ref()]).to_bytes()
+    }
+
+    fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        hashv(&[&[RECEIPT_NODE_PREFIX], left.as_ref(), right.as_ref()]).to_bytes()
+    }
+
+    fn receipt_tree() -> ([[u8; 32]; 4], [u8; 32], [u8; 32], [u8; 32]) {
+        let leaves = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let left = node_hash(&leaf_hash(&leaves[0]), &leaf_hash(&leaves[1]));
+        let right = node_hash(&leaf_hash(&leaves[2]), &leaf_hash(&leaves[3]));
+        (leaves, left, right, node_hash(&left, &right))
+    }
+
+    #[test]
+    fn receipt_proof_known_root() {
+        let (leaves, left, right, root) = receipt_tree();
+        let proofs = [
+            [leaf_hash(&leaves[1]), right],
+            [leaf_hash(&leaves[0]), right],
+            [leaf_hash(&leaves[3]), left],
+            [leaf_hash(&leaves[2]), left],
+        ];
+        for (index, (leaf, proof)) in leaves.iter().zip(proofs.iter()).enumerate() {
+            assert_eq!(receipt_root_from_proof(leaf, index as u32, proof), Some(root));
+            assert!(verify_receipt_proof(&root, leaf, index as u32, proof));
+        }
+        assert_eq!(receipt_root_from_proof(&leaves[0], 0, &[]), Some(leaf_hash(&leaves[0])));
+    }
+
+    #[test]
+    fn receipt_proof_wrong_sibling() {
+        let (leaves, _, right, root) = receipt_tree();
+        let proof = [leaf_hash(&leaves[2]), right];
+        assert!(!verify_receipt_proof(&root, &leaves[0], 0, &proof));
+        let proof = [leaf_hash(&leaves[1]), [0u8; 32]];
+        assert!(!verify_receipt_proof(&root, &leaves[0], 0, &proof));
+    }
+
+    #[test]
+    fn receipt_proof_wrong_index() {
+        let (leaves, _, right, root) = receipt_tree();
+        let proof = [leaf_hash(&leaves[1]), right];
+        assert!(!verify_receipt_proof(&root, &leaves[0], 1, &proof));
+        assert!(!verify_receipt_proof(&root, &leaves[0], 2, &proof));
+        assert_eq!(receipt_root_from_proof(&leaves[0], 4, &proof), None);
+        assert_eq!(receipt_root_from_proof(&leaves[0], u32::MAX, &proof), None);
+    }
+
+    #[test]
+    fn receipt_proof_rejects_internal_node_as_leaf() {
+        let (_, left, right, root) = receipt_tree();
+        assert!(!verify_receipt_proof(&root, &left, 0, &[right]));
+    }
+
+    #[test]
+    fn receipt_proof_depth_limit() {
+        let proof = vec![[0u8; 32]; MAX_RECEIPT_PROOF_DEPTH];
+        assert!(receipt_root_from_proof(&[0u8; 32], u32::MAX, &proof).is_some());
+        let proof = vec![[0u8; 32]; MAX_RECEIPT_PROOF_DEPTH + 1];
+        assert_eq!(receipt_root_from_proof(&[0u8; 32], 0, &proof), None);
+    }
+
+    fn ed25519_data(pubkey: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let current = u16::MAX.to_le_bytes();
+        let mut data = vec![1u8, 0];
+        data.extend_from_slice(&48u16.to_le_bytes());
+        data.extend_from_slice(&current);
+        data.extend_from_slice(&16u16.to_le_bytes());
+        data.extend_from_slice(&current);
+        data.extend_from_slice(&112u16.to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.extend_from_slice(&current);
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[7u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn attestation() -> (Pubkey, Vec<u8>) {
+        let message = attestation_message(
+            &Pubkey::new_unique(),
+            1,
+            &[2u8; 32],
+            &[3u8; 32],
+            &[4u8; 32],
+            "model",
+        );
+        (Pubkey::new_unique(), message)
+    }
+
+    fn assert_bad_attestation(result: Result<Pubkey>) {
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::BadAttestation));
+    }
+
+    #[test]
+    fn ed25519_signer_reads_standard_layout() {
+        let (attestor, message) = attestation();
+        let data = ed25519_data(&attestor, &message);
+        assert_eq!(ed25519_signer(&data, &message).unwrap(), attestor);
+    }
+
+    #[test]
+    fn ed25519_signer_rejects_foreign_instruction_index() {
+        let (attestor, message) = attestation();
+        for at in [4, 8, 14] {
+            let mut data = ed25519_data(&attestor, &message);
+            data[at..at + 2].copy_from_slice(&0u16.to_le_bytes());
+            assert_bad_attestation(ed25519_signer(&data, &message));
+        }
+    }
+
+    #[test]
+    fn ed25519_signer_rejects_truncated_data() {
+        let (attestor, message) = attestation();
+        let data = ed25519_data(&attestor, &message);
+        assert_bad_attestation(ed25519_signer(&data[..15], &message));
+        assert_bad_attestation(ed25519_signer(&data[..data.len() - 1], &message));
+        assert_bad_attestation(ed25519_signer(&[], &message));
+
+        let mut data = ed25519_data(&attestor, &message);
+        let len = data.len() as u16;
+        data[6..8].copy_from_slice(&(len - 31).to_le_bytes());
+        assert_bad_attestation(ed25519_signer(&data, &message));
+    }
+
+    #[test]
+    fn ed25519_signer_rejects_signature_count() {
+        let (attestor, message) = attestation();
+        for count in [0u8, 2] {
+            let mut data = ed25519_data(&attestor, &message);
+            data[0] = count;
+            assert_bad_attestation(ed25519_signer(&data, &message));
+        }
+    }
+
+    #[test]
+    fn ed25519_signer_rejects_message_mismatch() {
+        let (attestor, message) = attestation();
+        let data = ed25519_data(&attestor, &message);
+        let mut other = message.clone();
+        other[32] ^= 1;
+        assert_bad_attestation(ed25519_signer(&data, &other));
+        assert_bad_attestation(ed25519_signer(&data, &message[..message.len() - 1]));
+    }
 }
@@ -2,7 +2,20 @@ import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { AmbientApiError, callAmbient } from "./ambient";
 import { getProgram } from "./anchor";
-import { ensureTreasury, extractVotesSummary, getProposalText } from "./governance";
+import { ensureConfig } from "./config";
+import {
+  commitJudgeVerdict,
+  completeActions,
+  ensureJudges,
+  ensureTreasury,
+  extractVotesSummary,
+  finalizeConsensus,
+  generateJudges,
+  getProposalText,
+  JudgeReveal,
+  revealJudgeVerdicts,
+  waitForVotingEnd,
+} from "./governance";
 import { buildJudgePrompt } from "./prompts";
 import {
   getArgOrExit,
@@ -11,6 +24,7 @@ import {
   normalizeVerdict,
   parseJsonBlock,
   requireEnv,
+  sha256Bytes,
   usage,
 } from "./utils";
 import { ACTION_LAMPORTS, TREASURY_TOPUP_LAMPORTS } from "./constants";
@@ -44,6 +58,7 @@ async function main() {
   const votes = extractVotesSummary(proposal as any);
   const prompt = buildJudgePrompt(getProposalText(proposal), votes);
 
+  await ensureConfig(program as any, provider);
   await ensureTreasury(
     program as any,
     provider,
@@ -51,12 +66,15 @@ async function main() {
     TREASURY_TOPUP_LAMPORTS
   );
 
-  const judges = [
-    anchor.web3.Keypair.generate(),
-    anchor.web3.Keypair.generate(),
-    anchor.web3.Keypair.generate(),
-  ];
+  await waitForVotingEnd(proposal);
+  const judges = generateJudges(proposal);
+  await ensureJudges(
+    program as any,
+    provider,
+    judges.map((j) => j.publicKey)
+  );
 
+  const reveals: JudgeReveal[] = [];
   for (let i = 0; i < judges.length; i++) {
     let ambientResult;
     try {
@@ -76,38 +94,25 @@ async function main() {
     }
 
     const verdictCode = parseResponse(responseText);
-    await program.methods
-      .submitJudgeResult(verdictCode)
-      .accounts({
-        proposal: proposalPda,
-        payer: user,
-        judge: judges[i].publicKey,
+    reveals.push(
+      await commitJudgeVerdict(program as any, proposalPda, user, judges[i], verdictCode, {
+        promptHash: sha256Bytes(prompt),
+        modelId: MODEL_ID,
+        rationaleHash: sha256Bytes(responseText),
+        receiptRoot: receiptRootBytes,
       })
-      .signers([judges[i]])
-      .rpc();
+    );
 
     console.log(`judge_${i + 1}_verdict:`, verdictCode);
     logReceipt(`judge_${i + 1}`, receiptPresent, receiptRootBytes);
   }
+  await revealJudgeVerdicts(program as any, proposalPda, reveals);
 
-  await program.methods
-    .finalizeConsensus()
-    .accounts({
-      proposal: proposalPda,
-      finalizer: user,
-    })
-    .rpc();
+  await finalizeConsensus(program as any, proposalPda, user);
 
   const updated = await program.account.proposal.fetch(proposalPda);
   if (updated.finalVerdict === 1) {
-    await program.methods
-      .completeAction()
-      .accounts({
-        proposal: proposalPda,
-        recipient: updated.authority,
-        executor: user,
-      })
-      .rpc();
+    await completeActions(program as any, proposalPda, user);
   }
 
   console.log("proposal:", proposalPda.toBase58());
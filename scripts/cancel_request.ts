@@ -0,0 +1,35 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { usage } from "./utils";
+
+const USAGE = usage("cancel_request.ts", "<judge | proposal> <REQUEST_PDA>");
+
+async function main() {
+  const [kind, requestArg] = process.argv.slice(2);
+  if (!requestArg || (kind !== "judge" && kind !== "proposal")) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const authority = provider.wallet.publicKey;
+  const requestPda = new anchor.web3.PublicKey(requestArg);
+
+  const method =
+    kind === "judge" ? program.methods.cancelJudgeRequest() : program.methods.cancelProposalRequest();
+  const sig = await method
+    .accounts({
+      request: requestPda,
+      authority,
+    })
+    .rpc();
+
+  console.log("cancelled:", requestPda.toBase58());
+  console.log("tx:", sig);
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
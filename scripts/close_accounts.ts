@@ -0,0 +1,61 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { closeProposalAccounts } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "close_accounts.ts",
+  "<judge-request <REQUEST_PDA> | relayer-result <REQUEST_PDA> | proposal-request <REQUEST_PDA> | proposal <PROPOSAL_PDA>>"
+);
+
+async function main() {
+  const [command, target] = process.argv.slice(2);
+  if (!command || !target) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+  const targetPda = new anchor.web3.PublicKey(target);
+
+  if (command === "judge-request") {
+    await program.methods
+      .closeJudgeRequest()
+      .accounts({
+        request: targetPda,
+        authority: signer,
+      })
+      .rpc();
+  } else if (command === "relayer-result") {
+    await program.methods
+      .closeRelayerResult()
+      .accounts({
+        request: targetPda,
+        relayer: signer,
+      })
+      .rpc();
+  } else if (command === "proposal-request") {
+    await program.methods
+      .closeProposalRequest()
+      .accounts({
+        request: targetPda,
+        authority: signer,
+      })
+      .rpc();
+  } else if (command === "proposal") {
+    const closed = await closeProposalAccounts(program as any, targetPda, signer);
+    console.log("closed_accounts:", closed);
+  } else {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  console.log("closed:", command, targetPda.toBase58());
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { readFileSync } from "fs";
+
+export function getConfigPda(programId: anchor.web3.PublicKey): anchor.web3.PublicKey {
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    programId
+  );
+  return configPda;
+}
+
+export const PAUSE_FLAGS: Record<string, number> = {
+  oracle: 1 << 0,
+  governance: 1 << 1,
+  treasury: 1 << 2,
+};
+
+export function getRelayerEntryPda(
+  programId: anchor.web3.PublicKey,
+  relayer: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [entryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("relayer"), relayer.toBuffer()],
+    programId
+  );
+  return entryPda;
+}
+
+export async function resolveRelayerEntry(
+  program: anchor.Program,
+  relayer: anchor.web3.PublicKey
+): Promise<anchor.web3.PublicKey | null> {
+  const entryPda = getRelayerEntryPda(program.programId, relayer);
+  const info = await program.provider.connection.getAccountInfo(entryPda);
+  return info ? entryPda : null;
+}
+
+export async function ensureConfig(
+  program: anchor.Program,
+  provider: anchor.AnchorProvider
+): Promise<anchor.web3.PublicKey> {
+  const configPda = getConfigPda(program.programId);
+  const info = await provider.connection.getAccountInfo(configPda);
+  if (!info) {
+    await program.methods
+      .initConfig(provider.wallet.publicKey)
+      .accounts({
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+  return configPda;
+}
+
+export async function getMinRequestFee(program: anchor.Program): Promise<anchor.BN> {
+  const cfg = await (program.account as any).config.fetch(getConfigPda(program.programId));
+  return cfg.minRequestFee;
+}
+
+export function attestationMessage(
+  request: anchor.web3.PublicKey,
+  decision: number,
+  responseHash: number[],
+  receiptRoot: number[],
+  promptHash: number[],
+  modelId: string
+): Buffer {
+  return Buffer.concat([
+    request.toBuffer(),
+    Buffer.from([decision]),
+    Buffer.from(responseHash),
+    Buffer.from(receiptRoot),
+    Buffer.from(promptHash),
+    Buffer.from(modelId, "utf8"),
+  ]);
+}
+
+export function attestationInstructions(message: Buffer): anchor.web3.TransactionInstruction[] {
+  const keypairPath = process.env.ATTESTOR_KEYPAIR;
+  if (!keypairPath) {
+    return [];
+  }
+  const attestor = anchor.web3.Keypair.fromSecretKey(
+    Uint8Array.from(JSON.parse(readFileSync(keypairPath, "utf8")))
+  );
+  return [
+    anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: attestor.secretKey,
+      message,
+    }),
+  ];
+}
+
+export function callbackAccounts(
+  programId: anchor.web3.PublicKey,
+  requestPda: anchor.web3.PublicKey,
+  req: any
+): anchor.web3.AccountMeta[] {
+  const [callbackAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("callback"), requestPda.toBuffer()],
+    programId
+  );
+  return [
+    { pubkey: req.callbackProgram, isSigner: false, isWritable: false },
+    { pubkey: callbackAuthority, isSigner: false, isWritable: false },
+    ...req.callbackAccounts.map((a: any) => ({
+      pubkey: a.pubkey,
+      isSigner: false,
+      isWritable: a.isWritable,
+    })),
+  ];
+}
+
+export async function deliverCallback(
+  program: anchor.Program,
+  requestPda: anchor.web3.PublicKey
+): Promise<string> {
+  const req = await (program.account as any).judgeRequest.fetch(requestPda);
+  return program.methods
+    .deliverCallback()
+    .accounts({ request: requestPda } as any)
+    .remainingAccounts(callbackAccounts(program.programId, requestPda, req))
+    .rpc();
+}
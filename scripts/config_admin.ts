@@ -0,0 +1,171 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { getConfigPda, getRelayerEntryPda, PAUSE_FLAGS } from "./config";
+import { getTreasuryPda } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "config_admin.ts",
+  "<show | update-relayer <PUBKEY> | add-relayer <PUBKEY> | remove-relayer <PUBKEY> | add-attestor <PUBKEY> | remove-attestor <PUBKEY> | set-min-fee <LAMPORTS> | set-dispute <CHALLENGE_SECS> <BOND_LAMPORTS> [RESOLUTION_SECS] | set-action-cap <LAMPORTS> | propose-admin <PUBKEY> | accept-admin | pause [oracle|governance|treasury ...] | unpause>"
+);
+
+async function main() {
+  const [command, arg, ...rest] = process.argv.slice(2);
+  if (!command) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+  const configPda = getConfigPda(program.programId);
+
+  const requireArg = (): anchor.web3.PublicKey => {
+    if (!arg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    return new anchor.web3.PublicKey(arg);
+  };
+
+  if (command === "update-relayer") {
+    await program.methods
+      .updateRelayer(requireArg())
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "add-relayer") {
+    await program.methods
+      .addRelayer(requireArg())
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "remove-relayer") {
+    await program.methods
+      .removeRelayer()
+      .accounts({
+        relayerEntry: getRelayerEntryPda(program.programId, requireArg()),
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "add-attestor") {
+    await program.methods
+      .addAttestor(requireArg())
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "remove-attestor") {
+    await program.methods
+      .removeAttestor(requireArg())
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "set-min-fee") {
+    if (!arg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    await program.methods
+      .setMinRequestFee(new anchor.BN(arg))
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "set-dispute") {
+    if (!arg || rest.length === 0) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const current = await program.account.config.fetch(configPda);
+    const resolutionSecs = rest[1] ? new anchor.BN(rest[1]) : current.disputeResolutionSecs;
+    await program.methods
+      .setDisputeParams(new anchor.BN(arg), new anchor.BN(rest[0]), resolutionSecs)
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "set-action-cap") {
+    if (!arg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    await program.methods
+      .setMaxActionLamports(new anchor.BN(arg))
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "propose-admin") {
+    await program.methods
+      .proposeAdmin(requireArg())
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "accept-admin") {
+    await program.methods
+      .acceptAdmin()
+      .accounts({
+        newAdmin: signer,
+      })
+      .rpc();
+  } else if (command === "pause") {
+    const names = arg ? [arg, ...rest] : [];
+    let flags = 0;
+    for (const name of names) {
+      if (!(name in PAUSE_FLAGS)) {
+        console.error(USAGE);
+        process.exit(1);
+      }
+      flags |= PAUSE_FLAGS[name];
+    }
+    await program.methods
+      .setPause(names.length === 0, flags)
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "unpause") {
+    await program.methods
+      .setPause(false, 0)
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command !== "show") {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const cfg = await program.account.config.fetch(configPda);
+  console.log("config:", configPda.toBase58());
+  console.log("admin:", cfg.admin.toBase58());
+  console.log("relayer:", cfg.relayer.toBase58());
+  console.log("pending_admin:", cfg.pendingAdmin.toBase58());
+  console.log("paused:", cfg.paused);
+  console.log("pause_flags:", cfg.pauseFlags);
+  console.log("min_request_fee:", cfg.minRequestFee.toString());
+  console.log("challenge_period_secs:", cfg.challengePeriodSecs.toString());
+  console.log("dispute_bond_lamports:", cfg.disputeBondLamports.toString());
+  console.log("dispute_resolution_secs:", cfg.disputeResolutionSecs.toString());
+  console.log(
+    "attestors:",
+    cfg.attestors.map((a: anchor.web3.PublicKey) => a.toBase58()).join(",") || "(none)"
+  );
+  const treasuryPda = getTreasuryPda(program.programId);
+  const treasury = await program.account.treasury.fetchNullable(treasuryPda);
+  console.log(
+    "max_action_lamports:",
+    treasury ? treasury.maxActionLamports.toString() : "(treasury not initialized)"
+  );
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -6,6 +6,25 @@ export const TREASURY_FUND_LAMPORTS = 2_000_000;
 export const TREASURY_TOPUP_LAMPORTS = 2_000_000;
 export const JUDGE_LAMPORTS = 2_000_000;
 
+export const DEFAULT_JUDGE_PANEL_SIZE = 3;
+export const DEFAULT_VOTING_PERIOD_SECS = 30;
+export const DEFAULT_JUDGING_PERIOD_SECS = 3600;
+export const DEFAULT_REVEAL_PERIOD_SECS = 30;
+export const DEFAULT_JUDGE_REVEAL_PERIOD_SECS = 3600;
+export const CONSENSUS_MODES: Record<string, number> = {
+  majority: 1,
+  supermajority: 2,
+  unanimity: 3,
+};
+export const DECISION_POLICIES: Record<string, number> = {
+  judges: 1,
+  votes: 2,
+  both: 3,
+  weighted: 4,
+};
+export const DEFAULT_APPROVAL_RATIO_BPS = 5000;
+export const DEFAULT_JUDGE_WEIGHT_BPS = 5000;
+
 export const MAX_SUMMARY_WORDS = 60;
 export const MAX_SUMMARY_CHARS = 400;
 export const MAX_LIST_ITEMS = 5;
@@ -1,11 +1,48 @@
 import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
 import { getProgram } from "./anchor";
-import { createProposalWithRevisionAndVote } from "./governance";
+import { ensureConfig } from "./config";
+import {
+  createProposalWithRevisionAndVote,
+  defaultProposalActions,
+  ensureGovernanceConfig,
+  ensureTreasury,
+  ProposalAction,
+} from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "create_governance_proposal.ts",
+  "[--atomic] [LAMPORTS:RECIPIENT ...]"
+);
+
+function parseAction(arg: string): ProposalAction {
+  const [lamports, recipient] = arg.split(":");
+  if (!lamports || !recipient) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+  return {
+    amountLamports: new anchor.BN(lamports),
+    recipient: new anchor.web3.PublicKey(recipient),
+  };
+}
 
 async function main() {
+  const args = process.argv.slice(2);
+  const atomic = args.includes("--atomic");
+  const actionArgs = args.filter((arg) => arg !== "--atomic");
+
   const { provider, program } = getProgram();
   const user = provider.wallet.publicKey;
 
+  await ensureConfig(program as any, provider);
+  await ensureGovernanceConfig(program as any, provider);
+  await ensureTreasury(program as any, provider, 0, 0);
+
+  const actions =
+    actionArgs.length > 0 ? actionArgs.map(parseAction) : defaultProposalActions(user);
+
   const proposalText =
     "Minimal proposal: fund an automation action after approval.";
   const revisionText = "Revision 1: clarify the action recipient.";
@@ -14,10 +51,22 @@ async function main() {
     user,
     proposalText,
     revisionText,
-    1
+    1,
+    undefined,
+    actions,
+    atomic
   );
 
   console.log("proposal:", proposalPda.toBase58());
+  console.log("atomic_actions:", atomic);
+  actions.forEach((action, i) => {
+    console.log(
+      `action_${i}:`,
+      action.amountLamports.toString(),
+      "->",
+      action.recipient.toBase58()
+    );
+  });
 }
 
 main().catch((e) => {
@@ -2,6 +2,7 @@ import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { createHash } from "crypto";
 import { getProgram } from "./anchor";
+import { getMinRequestFee } from "./config";
 import { fetchProposalFromUrl, ProposalDetails } from "./governance_sources";
 import { getArgOrExit, usage } from "./utils";
 
@@ -103,6 +104,9 @@ async function main() {
   const { provider, program } = getProgram();
   const user = provider.wallet.publicKey;
   await ensureConfig(program as any, user);
+  const fee = process.env.REQUEST_FEE_LAMPORTS
+    ? new anchor.BN(process.env.REQUEST_FEE_LAMPORTS)
+    : await getMinRequestFee(program as any);
 
   const nonce = new anchor.BN(Date.now());
   const [requestPda] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -111,7 +115,7 @@ async function main() {
   );
 
   await program.methods
-    .createProposalRequest(proposal.source, proposal.proposal_id, canonicalText, nonce)
+    .createProposalRequest(proposal.source, proposal.proposal_id, canonicalText, nonce, fee)
     .accounts({
       user,
     })
@@ -121,6 +125,7 @@ async function main() {
   console.log("source:", proposal.source);
   console.log("proposal_id:", proposal.proposal_id);
   console.log("body_truncated:", truncated);
+  console.log("fee_lamports:", fee.toString());
 }
 
 main().catch((e) => {
@@ -0,0 +1,106 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import {
+  delegatedVoteAccounts,
+  getDelegationPda,
+  getVoteRecordPda,
+  resolveVoterDeposit,
+} from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "delegation.ts",
+  "<delegate <DELEGATE> [MINT] [PROPOSAL_SCOPE] [EXPIRES_AT] | undelegate [MINT] | vote <PROPOSAL_PDA> <CHOICE> <DELEGATOR...>>"
+);
+
+function pubkeyOrDefault(value?: string): anchor.web3.PublicKey {
+  return value && value !== "none"
+    ? new anchor.web3.PublicKey(value)
+    : anchor.web3.PublicKey.default;
+}
+
+async function main() {
+  const [command, ...rest] = process.argv.slice(2);
+  if (!command) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+
+  if (command === "delegate") {
+    const [delegateArg, mintArg, scopeArg, expiresArg] = rest;
+    if (!delegateArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const mint = pubkeyOrDefault(mintArg);
+    await program.methods
+      .delegate(
+        mint,
+        new anchor.web3.PublicKey(delegateArg),
+        pubkeyOrDefault(scopeArg),
+        new anchor.BN(expiresArg ?? 0)
+      )
+      .accounts({
+        delegator: signer,
+      })
+      .rpc();
+    const delegationPda = getDelegationPda(program.programId, mint, signer);
+    const delegation = await program.account.delegation.fetch(delegationPda);
+    console.log("delegation:", delegationPda.toBase58());
+    console.log("delegate:", delegation.delegate.toBase58());
+    console.log("mint:", delegation.mint.toBase58());
+    console.log("scope:", delegation.scope.toBase58());
+    console.log("expires_at:", delegation.expiresAt.toString());
+  } else if (command === "undelegate") {
+    const mint = pubkeyOrDefault(rest[0]);
+    await program.methods
+      .undelegate()
+      .accounts({
+        delegation: getDelegationPda(program.programId, mint, signer),
+        delegator: signer,
+      } as any)
+      .rpc();
+    console.log("undelegated");
+  } else if (command === "vote") {
+    const [proposalArg, choiceArg, ...delegatorArgs] = rest;
+    if (!proposalArg || !choiceArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const proposalPda = new anchor.web3.PublicKey(proposalArg);
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    const delegators = delegatorArgs.map((d) => new anchor.web3.PublicKey(d));
+    await program.methods
+      .castVote(Number(choiceArg))
+      .accounts({
+        proposal: proposalPda,
+        voterDeposit: resolveVoterDeposit(program.programId, proposal, signer),
+        voter: signer,
+      } as any)
+      .remainingAccounts(
+        delegatedVoteAccounts(program.programId, proposalPda, proposal, delegators)
+      )
+      .rpc();
+    const record = await program.account.voteRecord.fetch(
+      getVoteRecordPda(program.programId, proposalPda, signer)
+    );
+    console.log("weight:", record.weight.toString());
+    console.log("delegated_weight:", record.delegatedWeight.toString());
+    console.log(
+      "delegators:",
+      record.delegators.map((d: anchor.web3.PublicKey) => d.toBase58()).join(",")
+    );
+  } else {
+    console.error(USAGE);
+    process.exit(1);
+  }
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,28 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { deliverCallback } from "./config";
+import { usage } from "./utils";
+
+const USAGE = usage("deliver_callback.ts", "<REQUEST_PDA>");
+
+async function main() {
+  const [requestArg] = process.argv.slice(2);
+  if (!requestArg) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { program } = getProgram();
+  const requestPda = new anchor.web3.PublicKey(requestArg);
+
+  const sig = await deliverCallback(program as any, requestPda);
+
+  console.log("callback delivered:", requestPda.toBase58());
+  console.log("tx:", sig);
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
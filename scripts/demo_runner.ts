@@ -2,13 +2,24 @@ import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { AmbientApiError, callAmbient } from "./ambient";
 import { getProgram } from "./anchor";
+import { ensureConfig } from "./config";
 import {
+  closeProposalAccounts,
+  commitJudgeVerdict,
+  completeActions,
   createProposalWithRevisionAndVote,
+  ensureGovernanceConfig,
+  ensureJudges,
   ensureTreasury,
   extractVotesSummary,
+  generateJudges,
   fetchGovernanceState,
+  finalizeConsensus,
   getProposalText,
+  JudgeReveal,
   logGovernanceState,
+  revealJudgeVerdicts,
+  waitForVotingEnd,
 } from "./governance";
 import { buildJudgePrompt } from "./prompts";
 import {
@@ -21,6 +32,7 @@ import {
   normalizeVerdict,
   parseJsonBlock,
   requireEnv,
+  sha256Bytes,
 } from "./utils";
 
 function parseVerdict(text: string): number {
@@ -35,6 +47,7 @@ async function main() {
   const args = process.argv.slice(2);
   const skipJudges = args.includes("--skip-judges");
   const skipAction = args.includes("--skip-action");
+  const closeAccounts = args.includes("--close");
   const proposalIndex = args.indexOf("--proposal");
   const proposalArg =
     proposalIndex !== -1 && args[proposalIndex + 1] ? args[proposalIndex + 1] : null;
@@ -45,6 +58,8 @@ async function main() {
   const { provider, program } = getProgram();
   const user = provider.wallet.publicKey;
 
+  await ensureConfig(program as any, provider);
+  await ensureGovernanceConfig(program as any, provider);
   await ensureTreasury(
     program as any,
     provider,
@@ -73,12 +88,15 @@ async function main() {
   const prompt = buildJudgePrompt(getProposalText(proposal), votes);
 
   if (!skipJudges) {
-    const judges = [
-      anchor.web3.Keypair.generate(),
-      anchor.web3.Keypair.generate(),
-      anchor.web3.Keypair.generate(),
-    ];
+    await waitForVotingEnd(proposal);
+    const judges = generateJudges(proposal);
+    await ensureJudges(
+      program as any,
+      provider,
+      judges.map((j) => j.publicKey)
+    );
 
+    const reveals: JudgeReveal[] = [];
     for (let i = 0; i < judges.length; i++) {
       let ambientResult;
       try {
@@ -98,43 +116,35 @@ async function main() {
       }
 
       const verdictCode = parseVerdict(responseText);
-      await program.methods
-        .submitJudgeResult(verdictCode)
-        .accounts({
-          proposal: proposalPda,
-          payer: user,
-          judge: judges[i].publicKey,
+      reveals.push(
+        await commitJudgeVerdict(program as any, proposalPda, user, judges[i], verdictCode, {
+          promptHash: sha256Bytes(prompt),
+          modelId: MODEL_ID,
+          rationaleHash: sha256Bytes(responseText),
+          receiptRoot: receiptRootBytes,
         })
-        .signers([judges[i]])
-        .rpc();
+      );
 
       console.log(`judge_${i + 1}_verdict:`, verdictCode);
       logReceipt(`judge_${i + 1}`, receiptPresent, receiptRootBytes);
     }
+    await revealJudgeVerdicts(program as any, proposalPda, reveals);
 
-    await program.methods
-      .finalizeConsensus()
-      .accounts({
-        proposal: proposalPda,
-        finalizer: user,
-      })
-      .rpc();
+    await finalizeConsensus(program as any, proposalPda, user);
   }
 
   const updated = await program.account.proposal.fetch(proposalPda);
   if (!skipAction && updated.finalVerdict === 1) {
-    await program.methods
-      .completeAction()
-      .accounts({
-        proposal: proposalPda,
-        recipient: updated.authority,
-        executor: user,
-      })
-      .rpc();
+    await completeActions(program as any, proposalPda, user);
   }
 
   const state = await fetchGovernanceState(program as any, proposalPda);
   logGovernanceState(proposalPda, state);
+
+  if (closeAccounts && updated.status === 1) {
+    const closed = await closeProposalAccounts(program as any, proposalPda, user);
+    console.log("closed_accounts:", closed);
+  }
 }
 
 main().catch((e) => {
@@ -0,0 +1,130 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { attestationInstructions, attestationMessage, resolveRelayerEntry } from "./config";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "dispute.ts",
+  "<dispute <PROPOSAL_REQUEST_PDA> | resolve <PROPOSAL_REQUEST_PDA> <VERDICT> [SUMMARY_HASH_HEX] [RECEIPT_ROOT_HEX] [PROMPT_HASH_HEX] [MODEL_ID] | expire <PROPOSAL_REQUEST_PDA> | finalize <PROPOSAL_REQUEST_PDA>>"
+);
+
+function hexBytes(hex: string | undefined, stored: number[]): number[] {
+  if (!hex) {
+    return Array.from(stored);
+  }
+  return Array.from(Buffer.from(hex.replace(/^0x/, ""), "hex"));
+}
+
+function getDisputePda(
+  programId: anchor.web3.PublicKey,
+  request: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [disputePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("dispute"), request.toBuffer()],
+    programId
+  );
+  return disputePda;
+}
+
+async function main() {
+  const [
+    command,
+    requestArg,
+    verdictArg,
+    summaryHashArg,
+    receiptRootArg,
+    promptHashArg,
+    modelIdArg,
+  ] = process.argv.slice(2);
+  if (!command || !requestArg) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+  const requestPda = new anchor.web3.PublicKey(requestArg);
+  const req = await program.account.proposalRequest.fetch(requestPda);
+
+  if (command === "dispute") {
+    await program.methods
+      .disputeProposalRequest()
+      .accounts({
+        request: requestPda,
+        disputer: signer,
+      })
+      .rpc();
+  } else if (command === "resolve") {
+    if (!verdictArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const disputePda = getDisputePda(program.programId, requestPda);
+    const dispute = await program.account.dispute.fetch(disputePda);
+    const verdict = Number(verdictArg);
+    const summaryHash = hexBytes(summaryHashArg, req.summaryHash);
+    const receiptRoot = hexBytes(receiptRootArg, req.receiptRoot);
+    const promptHash = hexBytes(promptHashArg, req.promptHash);
+    const modelId = modelIdArg ?? req.modelId;
+    await program.methods
+      .resolveDispute(verdict, summaryHash, receiptRoot, promptHash, modelId)
+      .accounts({
+        request: requestPda,
+        disputer: dispute.disputer,
+        relayer: req.relayer,
+        authority: req.authority,
+        relayerEntry: await resolveRelayerEntry(program as any, signer),
+        resolver: signer,
+      } as any)
+      .preInstructions(
+        attestationInstructions(
+          attestationMessage(
+            requestPda,
+            verdict,
+            summaryHash,
+            receiptRoot,
+            promptHash,
+            modelId
+          )
+        )
+      )
+      .rpc();
+  } else if (command === "expire") {
+    const disputePda = getDisputePda(program.programId, requestPda);
+    const dispute = await program.account.dispute.fetch(disputePda);
+    const sig = await program.methods
+      .expireDispute()
+      .accounts({
+        request: requestPda,
+        authority: req.authority,
+        disputer: dispute.disputer,
+      } as any)
+      .rpc();
+    console.log("dispute expired, request closed:", requestPda.toBase58());
+    console.log("tx:", sig);
+    return;
+  } else if (command === "finalize") {
+    await program.methods
+      .finalizeProposalRequest()
+      .accounts({
+        request: requestPda,
+        relayer: req.relayer,
+      })
+      .rpc();
+  } else {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const updated = await program.account.proposalRequest.fetch(requestPda);
+  console.log("proposal_request:", requestPda.toBase58());
+  console.log("status:", updated.status);
+  console.log("verdict_code:", updated.verdictCode);
+  console.log("challenge_ends_at:", updated.challengeEndsAt.toString());
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -1,39 +1,34 @@
 import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { getProgram } from "./anchor";
-import { getActionPda } from "./governance";
-import { getArgOrExit, usage } from "./utils";
+import { completeActions, fetchGovernanceState } from "./governance";
+import { usage } from "./utils";
 
 async function main() {
-  const proposalPdaStr = getArgOrExit(
-    usage("execute_action.ts", "<PROPOSAL_PDA>")
-  );
+  const [proposalArg, indexArg] = process.argv.slice(2);
+  if (!proposalArg) {
+    console.error(usage("execute_action.ts", "<PROPOSAL_PDA> [ACTION_INDEX]"));
+    process.exit(1);
+  }
 
   const { provider, program } = getProgram();
-  const proposalPda = new anchor.web3.PublicKey(proposalPdaStr);
-  const proposal = await program.account.proposal.fetch(proposalPda);
-
-  const actionPda = getActionPda(program.programId, proposalPda);
-  const action = await program.account.actionRequest.fetch(actionPda);
+  const proposalPda = new anchor.web3.PublicKey(proposalArg);
+  const index = indexArg === undefined ? undefined : Number(indexArg);
 
-  if (action.status !== 0) {
-    console.log("action_status:", action.status);
-    return;
-  }
-
-  await program.methods
-    .completeAction()
-    .accounts({
-      proposal: proposalPda,
-      recipient: proposal.authority,
-      executor: provider.wallet.publicKey,
-    })
-    .rpc();
+  const completed = await completeActions(
+    program as any,
+    proposalPda,
+    provider.wallet.publicKey,
+    index
+  );
+  console.log("completed_actions:", completed);
 
-  const updated = await program.account.actionRequest.fetch(actionPda);
-  console.log("action_request:", actionPda.toBase58());
-  console.log("action_status:", updated.status);
-  console.log("action_executor:", updated.executor.toBase58());
+  const { actions, actionPdas } = await fetchGovernanceState(program as any, proposalPda);
+  actionPdas.forEach((actionPda, i) => {
+    console.log(`action_${i}_request:`, actionPda.toBase58());
+    console.log(`action_${i}_status:`, actions[i]?.status ?? "not_found");
+    if (actions[i]) console.log(`action_${i}_executor:`, actions[i].executor.toBase58());
+  });
 }
 
 main().catch((e) => {
@@ -1,4 +1,17 @@
 import * as anchor from "@coral-xyz/anchor";
+import { createHash, randomBytes } from "crypto";
+import {
+  ACTION_LAMPORTS,
+  CONSENSUS_MODES,
+  DECISION_POLICIES,
+  DEFAULT_APPROVAL_RATIO_BPS,
+  DEFAULT_JUDGE_PANEL_SIZE,
+  DEFAULT_JUDGE_REVEAL_PERIOD_SECS,
+  DEFAULT_JUDGE_WEIGHT_BPS,
+  DEFAULT_JUDGING_PERIOD_SECS,
+  DEFAULT_REVEAL_PERIOD_SECS,
+  DEFAULT_VOTING_PERIOD_SECS,
+} from "./constants";
 
 export function getProposalPda(
   programId: anchor.web3.PublicKey,
@@ -34,10 +47,11 @@ function getTreasuryCache(programId: anchor.web3.PublicKey): TreasuryCache {
 
 export function getActionPda(
   programId: anchor.web3.PublicKey,
-  proposalPda: anchor.web3.PublicKey
+  proposalPda: anchor.web3.PublicKey,
+  index: number
 ): anchor.web3.PublicKey {
   const [actionPda] = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("action"), proposalPda.toBuffer()],
+    [Buffer.from("action"), proposalPda.toBuffer(), Buffer.from([index])],
     programId
   );
   return actionPda;
@@ -95,66 +109,518 @@ export async function ensureTreasury(
   return { treasuryPda, vaultPda };
 }
 
+export function getGovernanceConfigPda(
+  programId: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [governanceConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("governance_config")],
+    programId
+  );
+  return governanceConfigPda;
+}
+
+export function defaultGovernanceParams(): any {
+  return {
+    judgePanelSize: DEFAULT_JUDGE_PANEL_SIZE,
+    consensusMode: CONSENSUS_MODES.majority,
+    votingPeriodSecs: new anchor.BN(DEFAULT_VOTING_PERIOD_SECS),
+    judgingPeriodSecs: new anchor.BN(DEFAULT_JUDGING_PERIOD_SECS),
+    decisionPolicy: DECISION_POLICIES.judges,
+    quorumVotes: new anchor.BN(0),
+    approvalRatioBps: DEFAULT_APPROVAL_RATIO_BPS,
+    judgeWeightBps: DEFAULT_JUDGE_WEIGHT_BPS,
+    governingMint: anchor.web3.PublicKey.default,
+    secretBallot: false,
+    revealPeriodSecs: new anchor.BN(DEFAULT_REVEAL_PERIOD_SECS),
+    judgeRevealPeriodSecs: new anchor.BN(DEFAULT_JUDGE_REVEAL_PERIOD_SECS),
+  };
+}
+
+export function voteCommitment(
+  choice: number,
+  salt: Buffer,
+  voter: anchor.web3.PublicKey
+): number[] {
+  return Array.from(
+    createHash("sha256")
+      .update(Buffer.from([choice]))
+      .update(salt)
+      .update(voter.toBuffer())
+      .digest()
+  );
+}
+
+export type JudgeReveal = {
+  judge: anchor.web3.Keypair;
+  verdict: number;
+  salt: Buffer;
+};
+
+export type JudgeProvenance = {
+  promptHash: number[];
+  modelId: string;
+  rationaleHash: number[];
+  receiptRoot: number[];
+};
+
+export function emptyProvenance(): JudgeProvenance {
+  const zero = new Array(32).fill(0);
+  return { promptHash: zero, modelId: "", rationaleHash: zero, receiptRoot: zero };
+}
+
+export async function commitJudgeVerdict(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  payer: anchor.web3.PublicKey,
+  judge: anchor.web3.Keypair,
+  verdict: number,
+  provenance: JudgeProvenance = emptyProvenance()
+): Promise<JudgeReveal> {
+  const salt = randomBytes(32);
+  await program.methods
+    .submitJudgeResult(
+      voteCommitment(verdict, salt, judge.publicKey),
+      provenance.promptHash,
+      provenance.modelId,
+      provenance.rationaleHash,
+      provenance.receiptRoot
+    )
+    .accounts({
+      proposal: proposalPda,
+      payer,
+      judge: judge.publicKey,
+    })
+    .signers([judge])
+    .rpc();
+  return { judge, verdict, salt };
+}
+
+export async function revealJudgeVerdicts(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  reveals: JudgeReveal[]
+): Promise<void> {
+  for (const { judge, verdict, salt } of reveals) {
+    await program.methods
+      .revealJudgeResult(verdict, Array.from(salt))
+      .accounts({
+        proposal: proposalPda,
+        judge: judge.publicKey,
+      })
+      .signers([judge])
+      .rpc();
+  }
+}
+
+export function getJudgeResultPda(
+  programId: anchor.web3.PublicKey,
+  proposal: anchor.web3.PublicKey,
+  judge: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [judgeResultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("judge"), proposal.toBuffer(), judge.toBuffer()],
+    programId
+  );
+  return judgeResultPda;
+}
+
+export function getProposalInstructionPda(
+  programId: anchor.web3.PublicKey,
+  proposal: anchor.web3.PublicKey,
+  index: number
+): anchor.web3.PublicKey {
+  const [instructionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal_ix"), proposal.toBuffer(), Buffer.from([index])],
+    programId
+  );
+  return instructionPda;
+}
+
+export async function insertProposalInstruction(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  authority: anchor.web3.PublicKey,
+  ix: anchor.web3.TransactionInstruction
+): Promise<number> {
+  const proposal = await (program as any).account.proposal.fetch(proposalPda);
+  const index: number = proposal.instructionCount;
+  await program.methods
+    .insertProposalInstruction(
+      index,
+      ix.programId,
+      ix.keys.map((key) => ({
+        pubkey: key.pubkey,
+        isSigner: key.isSigner,
+        isWritable: key.isWritable,
+      })),
+      ix.data
+    )
+    .accounts({
+      proposal: proposalPda,
+      authority,
+    })
+    .rpc();
+  return index;
+}
+
+export async function executeProposalInstructions(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  executor: anchor.web3.PublicKey,
+  index?: number
+): Promise<number> {
+  const accountNs: any = (program as any).account;
+  const proposal = await accountNs.proposal.fetch(proposalPda);
+  let executed = 0;
+  for (let i = 0; i < proposal.instructionCount; i++) {
+    if (index !== undefined && i !== index) continue;
+    const instructionPda = getProposalInstructionPda(program.programId, proposalPda, i);
+    const instruction = await accountNs.proposalInstruction.fetch(instructionPda);
+    if (instruction.executed) continue;
+    await program.methods
+      .executeInstruction(i)
+      .accounts({
+        proposal: proposalPda,
+        executor,
+      })
+      .remainingAccounts([
+        { pubkey: instruction.programId, isSigner: false, isWritable: false },
+        ...instruction.accounts.map((meta: any) => ({
+          pubkey: meta.pubkey,
+          isSigner: false,
+          isWritable: meta.isWritable,
+        })),
+      ])
+      .rpc();
+    executed++;
+  }
+  return executed;
+}
+
+export function getVoterDepositPda(
+  programId: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [depositPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("voter_deposit"), mint.toBuffer(), owner.toBuffer()],
+    programId
+  );
+  return depositPda;
+}
+
+export function getDepositVaultPda(
+  programId: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("deposit_vault"), mint.toBuffer()],
+    programId
+  );
+  return vaultPda;
+}
+
+export function getDelegationPda(
+  programId: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey,
+  delegator: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [delegationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("delegation"), mint.toBuffer(), delegator.toBuffer()],
+    programId
+  );
+  return delegationPda;
+}
+
+export function getVoteRecordPda(
+  programId: anchor.web3.PublicKey,
+  proposalPda: anchor.web3.PublicKey,
+  voter: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [recordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vote"), proposalPda.toBuffer(), voter.toBuffer()],
+    programId
+  );
+  return recordPda;
+}
+
+export function delegatedVoteAccounts(
+  programId: anchor.web3.PublicKey,
+  proposalPda: anchor.web3.PublicKey,
+  proposal: { governingMint: anchor.web3.PublicKey },
+  delegators: anchor.web3.PublicKey[]
+): anchor.web3.AccountMeta[] {
+  const mint = proposal.governingMint;
+  const weighted = !mint.equals(anchor.web3.PublicKey.default);
+  const metas: anchor.web3.AccountMeta[] = [];
+  for (const delegator of delegators) {
+    metas.push({
+      pubkey: getDelegationPda(programId, mint, delegator),
+      isSigner: false,
+      isWritable: false,
+    });
+    metas.push({
+      pubkey: getVoteRecordPda(programId, proposalPda, delegator),
+      isSigner: false,
+      isWritable: true,
+    });
+    if (weighted) {
+      metas.push({
+        pubkey: getVoterDepositPda(programId, mint, delegator),
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+  }
+  return metas;
+}
+
+export function resolveVoterDeposit(
+  programId: anchor.web3.PublicKey,
+  proposal: { governingMint: anchor.web3.PublicKey },
+  voter: anchor.web3.PublicKey
+): anchor.web3.PublicKey | null {
+  if (proposal.governingMint.equals(anchor.web3.PublicKey.default)) {
+    return null;
+  }
+  return getVoterDepositPda(programId, proposal.governingMint, voter);
+}
+
+export async function waitForVotingEnd(proposal: {
+  votingEndsAt?: any;
+  revealEndsAt?: any;
+}): Promise<void> {
+  const endsAt = Number((proposal.revealEndsAt ?? proposal.votingEndsAt)?.toString() ?? 0);
+  const waitMs = (endsAt + 2) * 1000 - Date.now();
+  if (waitMs > 0) {
+    console.log(`waiting ${Math.ceil(waitMs / 1000)}s for voting to end`);
+    await new Promise((resolve) => setTimeout(resolve, waitMs));
+  }
+}
+
+export async function ensureGovernanceConfig(
+  program: anchor.Program,
+  provider: anchor.AnchorProvider
+): Promise<anchor.web3.PublicKey> {
+  const governanceConfigPda = getGovernanceConfigPda(program.programId);
+  const info = await provider.connection.getAccountInfo(governanceConfigPda);
+  if (!info) {
+    await program.methods
+      .initGovernanceConfig(defaultGovernanceParams())
+      .accounts({
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+  return governanceConfigPda;
+}
+
+export function generateJudges(proposal: { judgePanelSize?: number }): anchor.web3.Keypair[] {
+  const panelSize = Number(proposal.judgePanelSize ?? DEFAULT_JUDGE_PANEL_SIZE);
+  return Array.from({ length: panelSize }, () => anchor.web3.Keypair.generate());
+}
+
+export function getJudgeRegistryPda(programId: anchor.web3.PublicKey): anchor.web3.PublicKey {
+  const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("judge_registry")],
+    programId
+  );
+  return registryPda;
+}
+
+export function getJudgeEntryPda(
+  programId: anchor.web3.PublicKey,
+  judge: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  const [entryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("judge_entry"), judge.toBuffer()],
+    programId
+  );
+  return entryPda;
+}
+
+export async function ensureJudges(
+  program: anchor.Program,
+  provider: anchor.AnchorProvider,
+  judges: anchor.web3.PublicKey[]
+): Promise<void> {
+  const authority = provider.wallet.publicKey;
+  const registryPda = getJudgeRegistryPda(program.programId);
+  const registryInfo = await provider.connection.getAccountInfo(registryPda);
+  if (!registryInfo) {
+    await program.methods
+      .initJudgeRegistry(authority)
+      .accounts({
+        admin: authority,
+      })
+      .rpc();
+  }
+  for (const judge of judges) {
+    const entryPda = getJudgeEntryPda(program.programId, judge);
+    const entryInfo = await provider.connection.getAccountInfo(entryPda);
+    if (!entryInfo) {
+      await program.methods
+        .addJudge(judge)
+        .accounts({
+          authority,
+          payer: authority,
+        })
+        .rpc();
+    }
+  }
+}
+
+export type ProposalAction = {
+  amountLamports: anchor.BN;
+  recipient: anchor.web3.PublicKey;
+};
+
+export function defaultProposalActions(authority: anchor.web3.PublicKey): ProposalAction[] {
+  return [{ amountLamports: new anchor.BN(ACTION_LAMPORTS), recipient: authority }];
+}
+
 export async function createProposalWithRevisionAndVote(
   program: anchor.Program,
   authority: anchor.web3.PublicKey,
   proposalText: string,
   revisionText: string,
   voteChoice: number,
-  nonce?: anchor.BN
+  nonce?: anchor.BN,
+  actions?: ProposalAction[],
+  atomicActions = false
 ): Promise<{ proposalPda: anchor.web3.PublicKey; nonce: anchor.BN }> {
   const usedNonce = nonce ?? new anchor.BN(Date.now());
+  const usedActions = actions ?? defaultProposalActions(authority);
   const proposalPda = getProposalPda(program.programId, authority, usedNonce);
 
   await program.methods
-    .createGovernanceProposal(proposalText, new anchor.BN(0), usedNonce)
+    .createGovernanceProposal(
+      proposalText,
+      new anchor.BN(0),
+      usedNonce,
+      usedActions,
+      atomicActions
+    )
     .accounts({
       user: authority,
     })
     .rpc();
 
   await program.methods
-    .addRevision(new anchor.BN(1), revisionText)
+    .addRevision(new anchor.BN(1), revisionText, usedActions)
     .accounts({
       proposal: proposalPda,
       user: authority,
     })
     .rpc();
 
+  const proposal = await (program as any).account.proposal.fetch(proposalPda);
   await program.methods
     .castVote(voteChoice)
     .accounts({
       proposal: proposalPda,
+      voterDeposit: resolveVoterDeposit(program.programId, proposal, authority),
       voter: authority,
-    })
+    } as any)
     .rpc();
 
   return { proposalPda, nonce: usedNonce };
 }
 
+function actionPdas(
+  programId: anchor.web3.PublicKey,
+  proposalPda: anchor.web3.PublicKey,
+  proposal: { actions: unknown[] }
+): anchor.web3.PublicKey[] {
+  return proposal.actions.map((_, index) => getActionPda(programId, proposalPda, index));
+}
+
+export async function finalizeConsensus(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  finalizer: anchor.web3.PublicKey
+): Promise<void> {
+  const proposal = await (program as any).account.proposal.fetch(proposalPda);
+  await program.methods
+    .finalizeConsensus()
+    .accounts({
+      proposal: proposalPda,
+      finalizer,
+    })
+    .remainingAccounts(
+      actionPdas(program.programId, proposalPda, proposal).map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      }))
+    )
+    .rpc();
+}
+
+export async function completeActions(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  executor: anchor.web3.PublicKey,
+  index?: number
+): Promise<number> {
+  const accountNs: any = (program as any).account;
+  const proposal = await accountNs.proposal.fetch(proposalPda);
+  const pdas = actionPdas(program.programId, proposalPda, proposal);
+  const actions = await accountNs.actionRequest.fetchMultiple(pdas);
+
+  if (proposal.atomicActions) {
+    if (actions.some((action: any) => !action || action.status !== 0)) return 0;
+    await program.methods
+      .completeAllActions()
+      .accounts({
+        proposal: proposalPda,
+        executor,
+      })
+      .remainingAccounts(
+        pdas.flatMap((pubkey, i) => [
+          { pubkey, isSigner: false, isWritable: true },
+          { pubkey: actions[i].recipient, isSigner: false, isWritable: true },
+        ])
+      )
+      .rpc();
+    return pdas.length;
+  }
+
+  let completed = 0;
+  for (let i = 0; i < pdas.length; i++) {
+    if (index !== undefined && i !== index) continue;
+    if (!actions[i] || actions[i].status !== 0) continue;
+    await program.methods
+      .completeAction(i)
+      .accounts({
+        proposal: proposalPda,
+        recipient: actions[i].recipient,
+        executor,
+      })
+      .rpc();
+    completed++;
+  }
+  return completed;
+}
+
 export async function fetchGovernanceState(
   program: anchor.Program,
   proposalPda: anchor.web3.PublicKey
 ): Promise<{
   proposal: any;
-  action: any | null;
-  actionPda: anchor.web3.PublicKey;
+  actions: (any | null)[];
+  actionPdas: anchor.web3.PublicKey[];
   vaultPda: anchor.web3.PublicKey;
   vaultLamports: number;
 }> {
   const accountNs: any = (program as any).account;
   const proposal = await accountNs.proposal.fetch(proposalPda);
-  const actionPda = getActionPda(program.programId, proposalPda);
-  let action: any | null = null;
-  try {
-    action = await accountNs.actionRequest.fetch(actionPda);
-  } catch {
-    action = null;
-  }
+  const pdas = actionPdas(program.programId, proposalPda, proposal);
+  const actions = await accountNs.actionRequest.fetchMultiple(pdas);
   const vaultPda = getTreasuryVaultPda(program.programId);
   const vaultLamports =
     (await program.provider.connection.getAccountInfo(vaultPda))?.lamports ?? 0;
-  return { proposal, action, actionPda, vaultPda, vaultLamports };
+  return { proposal, actions, actionPdas: pdas, vaultPda, vaultLamports };
 }
 
 export function getProposalText(proposal: { proposalText?: unknown }): string {
@@ -178,13 +644,13 @@ export function logGovernanceState(
   proposalPda: anchor.web3.PublicKey,
   state: {
     proposal: any;
-    action: any | null;
-    actionPda: anchor.web3.PublicKey;
+    actions: (any | null)[];
+    actionPdas: anchor.web3.PublicKey[];
     vaultPda: anchor.web3.PublicKey;
     vaultLamports: number;
   }
 ): void {
-  const { proposal, action, actionPda, vaultPda, vaultLamports } = state;
+  const { proposal, actions, actionPdas: pdas, vaultPda, vaultLamports } = state;
   console.log("proposal:", proposalPda.toBase58());
   console.log("authority:", proposal.authority.toBase58());
   console.log("status:", proposal.status);
@@ -195,17 +661,143 @@ export function logGovernanceState(
   console.log("judge_approve:", proposal.judgeApprove);
   console.log("judge_reject:", proposal.judgeReject);
   console.log("judge_needs:", proposal.judgeNeeds);
+  console.log("judge_panel_size:", proposal.judgePanelSize);
+  console.log("judge_threshold:", proposal.judgeThreshold);
+  console.log("voting_starts_at:", proposal.votingStartsAt?.toString());
+  console.log("voting_ends_at:", proposal.votingEndsAt?.toString());
+  console.log("reveal_ends_at:", proposal.revealEndsAt?.toString());
+  console.log("judging_ends_at:", proposal.judgingEndsAt?.toString());
+  console.log("judge_reveal_ends_at:", proposal.judgeRevealEndsAt?.toString());
+  console.log("judge_commits:", proposal.judgeCommits);
+  console.log("unrevealed_judges:", proposal.unrevealedJudges);
+  console.log("secret_ballot:", proposal.secretBallot);
+  console.log("unrevealed_votes:", proposal.unrevealedVotes?.toString());
+  console.log("unrevealed_weight:", proposal.unrevealedWeight?.toString());
+  console.log("decision_policy:", proposal.decisionPolicy);
+  console.log("quorum_votes:", proposal.quorumVotes?.toString());
+  console.log("approval_ratio_bps:", proposal.approvalRatioBps);
+  console.log("judge_weight_bps:", proposal.judgeWeightBps);
+  console.log("judge_verdict:", proposal.judgeVerdict);
+  console.log("vote_verdict:", proposal.voteVerdict);
+  console.log("governing_mint:", proposal.governingMint?.toBase58());
   console.log("final_verdict:", proposal.finalVerdict);
   console.log("proposal_text:", proposal.proposalText);
-  console.log("action_request:", actionPda.toBase58());
-  if (action) {
-    console.log("action_status:", action.status);
-    console.log("action_amount_lamports:", action.amountLamports);
-    console.log("action_recipient:", action.recipient.toBase58());
-    console.log("action_executor:", action.executor.toBase58());
-  } else {
-    console.log("action_status: not_found");
-  }
+  console.log("atomic_actions:", proposal.atomicActions);
+  pdas.forEach((actionPda, i) => {
+    const requested = proposal.actions[i];
+    const action = actions[i];
+    console.log(`action_${i}_request:`, actionPda.toBase58());
+    console.log(`action_${i}_amount_lamports:`, requested.amountLamports.toString());
+    console.log(`action_${i}_recipient:`, requested.recipient.toBase58());
+    if (action) {
+      console.log(`action_${i}_status:`, action.status);
+      console.log(`action_${i}_executor:`, action.executor.toBase58());
+    } else {
+      console.log(`action_${i}_status:`, "not_found");
+    }
+  });
   console.log("treasury_vault:", vaultPda.toBase58());
   console.log("treasury_vault_lamports:", vaultLamports);
 }
+
+async function accountsForProposal(
+  program: anchor.Program,
+  accountName: string,
+  proposalPda: anchor.web3.PublicKey
+): Promise<any[]> {
+  return (program.account as any)[accountName].all([
+    { memcmp: { offset: 8, bytes: proposalPda.toBase58() } },
+  ]);
+}
+
+export async function closeProposalAccounts(
+  program: anchor.Program,
+  proposalPda: anchor.web3.PublicKey,
+  signer: anchor.web3.PublicKey
+): Promise<number> {
+  const proposal = await (program.account as any).proposal.fetch(proposalPda);
+  const isAuthority = proposal.authority.equals(signer);
+  let closed = 0;
+
+  if (isAuthority) {
+    for (const { publicKey } of await accountsForProposal(program, "proposalRevision", proposalPda)) {
+      await program.methods
+        .closeRevision()
+        .accounts({
+          proposal: proposalPda,
+          revision: publicKey,
+          authority: signer,
+        } as any)
+        .rpc();
+      closed++;
+    }
+    for (const { account } of await accountsForProposal(
+      program,
+      "proposalInstruction",
+      proposalPda
+    )) {
+      if (!account.executed && proposal.finalVerdict === 1) continue;
+      await program.methods
+        .closeProposalInstruction(account.index)
+        .accounts({
+          proposal: proposalPda,
+          authority: signer,
+        })
+        .rpc();
+      closed++;
+    }
+  }
+
+  for (const { publicKey, account } of await accountsForProposal(
+    program,
+    "judgeResult",
+    proposalPda
+  )) {
+    if (!account.payer.equals(signer)) continue;
+    await program.methods
+      .closeJudgeResult()
+      .accounts({
+        proposal: proposalPda,
+        judgeResult: publicKey,
+        receiver: signer,
+      } as any)
+      .rpc();
+    closed++;
+  }
+
+  const pdas = actionPdas(program.programId, proposalPda, proposal);
+  const actions = await (program.account as any).actionRequest.fetchMultiple(pdas);
+  for (let i = 0; i < pdas.length; i++) {
+    if (!actions[i] || actions[i].status === 0 || !actions[i].payer.equals(signer)) continue;
+    await program.methods
+      .closeActionRequest(i)
+      .accounts({
+        proposal: proposalPda,
+        receiver: signer,
+      })
+      .rpc();
+    closed++;
+  }
+
+  for (const { publicKey, account } of await accountsForProposal(
+    program,
+    "voteRecord",
+    proposalPda
+  )) {
+    const payer = account.delegate.equals(anchor.web3.PublicKey.default)
+      ? account.voter
+      : account.delegate;
+    if (!payer.equals(signer)) continue;
+    await program.methods
+      .closeVoteRecord()
+      .accounts({
+        proposal: proposalPda,
+        voteRecord: publicKey,
+        receiver: signer,
+      } as any)
+      .rpc();
+    closed++;
+  }
+
+  return closed;
+}
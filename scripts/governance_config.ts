@@ -0,0 +1,132 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { CONSENSUS_MODES, DECISION_POLICIES } from "./constants";
+import { defaultGovernanceParams, getGovernanceConfigPda } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "governance_config.ts",
+  "[--panel <N>] [--mode majority|supermajority|unanimity] [--voting-secs <N>] [--judging-secs <N>] [--policy judges|votes|both|weighted] [--quorum <N>] [--approval-bps <N>] [--judge-weight-bps <N>] [--mint <MINT|none>] [--secret-ballot on|off] [--reveal-secs <N>] [--judge-reveal-secs <N>]"
+);
+
+function getFlag(args: string[], name: string): string | null {
+  const index = args.indexOf(name);
+  if (index === -1) return null;
+  const value = args[index + 1];
+  if (!value) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+  return value;
+}
+
+async function main() {
+  const args = process.argv.slice(2);
+  const { provider, program } = getProgram();
+  const admin = provider.wallet.publicKey;
+  const governanceConfigPda = getGovernanceConfigPda(program.programId);
+
+  const existing = await provider.connection.getAccountInfo(governanceConfigPda);
+  const params = existing
+    ? (await program.account.governanceConfig.fetch(governanceConfigPda)).params
+    : defaultGovernanceParams();
+
+  const panel = getFlag(args, "--panel");
+  if (panel !== null) {
+    params.judgePanelSize = Number(panel);
+  }
+  const mode = getFlag(args, "--mode");
+  if (mode !== null) {
+    if (!(mode in CONSENSUS_MODES)) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    params.consensusMode = CONSENSUS_MODES[mode];
+  }
+
+  const votingSecs = getFlag(args, "--voting-secs");
+  if (votingSecs !== null) {
+    params.votingPeriodSecs = new anchor.BN(votingSecs);
+  }
+  const judgingSecs = getFlag(args, "--judging-secs");
+  if (judgingSecs !== null) {
+    params.judgingPeriodSecs = new anchor.BN(judgingSecs);
+  }
+  const policy = getFlag(args, "--policy");
+  if (policy !== null) {
+    if (!(policy in DECISION_POLICIES)) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    params.decisionPolicy = DECISION_POLICIES[policy];
+  }
+  const quorum = getFlag(args, "--quorum");
+  if (quorum !== null) {
+    params.quorumVotes = new anchor.BN(quorum);
+  }
+  const approvalBps = getFlag(args, "--approval-bps");
+  if (approvalBps !== null) {
+    params.approvalRatioBps = Number(approvalBps);
+  }
+  const judgeWeightBps = getFlag(args, "--judge-weight-bps");
+  if (judgeWeightBps !== null) {
+    params.judgeWeightBps = Number(judgeWeightBps);
+  }
+  const mint = getFlag(args, "--mint");
+  if (mint !== null) {
+    params.governingMint =
+      mint === "none" ? anchor.web3.PublicKey.default : new anchor.web3.PublicKey(mint);
+  }
+  const secretBallot = getFlag(args, "--secret-ballot");
+  if (secretBallot !== null) {
+    params.secretBallot = secretBallot === "on";
+  }
+  const revealSecs = getFlag(args, "--reveal-secs");
+  if (revealSecs !== null) {
+    params.revealPeriodSecs = new anchor.BN(revealSecs);
+  }
+  const judgeRevealSecs = getFlag(args, "--judge-reveal-secs");
+  if (judgeRevealSecs !== null) {
+    params.judgeRevealPeriodSecs = new anchor.BN(judgeRevealSecs);
+  }
+
+  if (!existing) {
+    await program.methods
+      .initGovernanceConfig(params)
+      .accounts({
+        admin,
+      })
+      .rpc();
+  } else if (args.length > 0) {
+    await program.methods
+      .updateGovernanceConfig(params)
+      .accounts({
+        admin,
+      })
+      .rpc();
+  }
+
+  const updated = await program.account.governanceConfig.fetch(governanceConfigPda);
+  console.log("governance_config:", governanceConfigPda.toBase58());
+  console.log("judge_panel_size:", updated.params.judgePanelSize);
+  console.log("consensus_mode:", updated.params.consensusMode);
+  console.log("voting_period_secs:", updated.params.votingPeriodSecs.toString());
+  console.log("judging_period_secs:", updated.params.judgingPeriodSecs.toString());
+  console.log("decision_policy:", updated.params.decisionPolicy);
+  console.log("quorum_votes:", updated.params.quorumVotes.toString());
+  console.log("approval_ratio_bps:", updated.params.approvalRatioBps);
+  console.log("judge_weight_bps:", updated.params.judgeWeightBps);
+  console.log("governing_mint:", updated.params.governingMint.toBase58());
+  console.log("secret_ballot:", updated.params.secretBallot);
+  console.log("reveal_period_secs:", updated.params.revealPeriodSecs.toString());
+  console.log(
+    "judge_reveal_period_secs:",
+    updated.params.judgeRevealPeriodSecs.toString()
+  );
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
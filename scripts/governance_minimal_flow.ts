@@ -1,11 +1,21 @@
 import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { getProgram } from "./anchor";
+import { ensureConfig } from "./config";
 import {
+  commitJudgeVerdict,
+  completeActions,
   createProposalWithRevisionAndVote,
+  ensureGovernanceConfig,
+  ensureJudges,
   ensureTreasury,
   fetchGovernanceState,
+  finalizeConsensus,
+  generateJudges,
+  JudgeReveal,
   logGovernanceState,
+  revealJudgeVerdicts,
+  waitForVotingEnd,
 } from "./governance";
 import {
   ACTION_LAMPORTS,
@@ -32,6 +42,8 @@ async function main() {
   const { provider, program } = getProgram();
   const user = provider.wallet.publicKey;
 
+  await ensureConfig(program as any, provider);
+  await ensureGovernanceConfig(program as any, provider);
   await ensureTreasury(program as any, provider, 0, 0);
   await program.methods
     .fundTreasury(new anchor.BN(TREASURY_FUND_LAMPORTS))
@@ -51,61 +63,29 @@ async function main() {
     1
   );
 
-  const judges = [
-    anchor.web3.Keypair.generate(),
-    anchor.web3.Keypair.generate(),
-    anchor.web3.Keypair.generate(),
-  ];
+  const proposal = await program.account.proposal.fetch(proposalPda);
+  await waitForVotingEnd(proposal);
+  const judges = generateJudges(proposal);
   for (const judge of judges) {
     await fundWallet(provider, judge.publicKey, JUDGE_LAMPORTS);
   }
+  await ensureJudges(
+    program as any,
+    provider,
+    judges.map((j) => j.publicKey)
+  );
 
-  await program.methods
-    .submitJudgeResult(1)
-    .accounts({
-      proposal: proposalPda,
-      payer: user,
-      judge: judges[0].publicKey,
-    })
-    .signers([judges[0]])
-    .rpc();
-
-  await program.methods
-    .submitJudgeResult(1)
-    .accounts({
-      proposal: proposalPda,
-      payer: user,
-      judge: judges[1].publicKey,
-    })
-    .signers([judges[1]])
-    .rpc();
-
-  await program.methods
-    .submitJudgeResult(2)
-    .accounts({
-      proposal: proposalPda,
-      payer: user,
-      judge: judges[2].publicKey,
-    })
-    .signers([judges[2]])
-    .rpc();
-
-  await program.methods
-    .finalizeConsensus()
-    .accounts({
-      proposal: proposalPda,
-      finalizer: user,
-    })
-    .rpc();
+  const reveals: JudgeReveal[] = [];
+  for (let i = 0; i < judges.length; i++) {
+    const verdict = i < proposal.judgeThreshold ? 1 : 2;
+    reveals.push(
+      await commitJudgeVerdict(program as any, proposalPda, user, judges[i], verdict)
+    );
+  }
+  await revealJudgeVerdicts(program as any, proposalPda, reveals);
 
-  await program.methods
-    .completeAction()
-    .accounts({
-      proposal: proposalPda,
-      recipient: user,
-      executor: user,
-    })
-    .rpc();
+  await finalizeConsensus(program as any, proposalPda, user);
+  await completeActions(program as any, proposalPda, user);
 
   const state = await fetchGovernanceState(program as any, proposalPda);
   logGovernanceState(proposalPda, state);
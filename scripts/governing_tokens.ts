@@ -0,0 +1,68 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { getDepositVaultPda, getVoterDepositPda } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "governing_tokens.ts",
+  "<deposit <MINT> <AMOUNT> | withdraw <MINT> <AMOUNT> | show <MINT>>"
+);
+
+async function main() {
+  const [command, mintArg, amountArg] = process.argv.slice(2);
+  if (!command || !mintArg) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const owner = provider.wallet.publicKey;
+  const mint = new anchor.web3.PublicKey(mintArg);
+  const ownerTokenAccount = anchor.utils.token.associatedAddress({ mint, owner });
+  const depositPda = getVoterDepositPda(program.programId, mint, owner);
+
+  const requireAmount = (): anchor.BN => {
+    if (!amountArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    return new anchor.BN(amountArg);
+  };
+
+  if (command === "deposit") {
+    await program.methods
+      .depositGoverningTokens(requireAmount())
+      .accounts({
+        governingMint: mint,
+        ownerTokenAccount,
+        owner,
+      })
+      .rpc();
+  } else if (command === "withdraw") {
+    await program.methods
+      .withdrawGoverningTokens(requireAmount())
+      .accounts({
+        voterDeposit: depositPda,
+        depositVault: getDepositVaultPda(program.programId, mint),
+        ownerTokenAccount,
+        owner,
+      } as any)
+      .rpc();
+  } else if (command !== "show") {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const deposit = await program.account.voterDeposit.fetch(depositPda);
+  console.log("voter_deposit:", depositPda.toBase58());
+  console.log("owner:", deposit.owner.toBase58());
+  console.log("mint:", deposit.mint.toBase58());
+  console.log("amount:", deposit.amount.toString());
+  console.log("locked_until:", deposit.lockedUntil.toString());
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
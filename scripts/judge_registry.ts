@@ -0,0 +1,89 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { getJudgeEntryPda, getJudgeRegistryPda } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "judge_registry.ts",
+  "<init [AUTHORITY] | set-authority <PUBKEY> | add <JUDGE> | suspend <JUDGE> | resume <JUDGE> | remove <JUDGE> | show [JUDGE]>"
+);
+
+async function main() {
+  const [command, arg] = process.argv.slice(2);
+  if (!command) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+  const registryPda = getJudgeRegistryPda(program.programId);
+
+  const requireArg = (): anchor.web3.PublicKey => {
+    if (!arg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    return new anchor.web3.PublicKey(arg);
+  };
+
+  if (command === "init") {
+    await program.methods
+      .initJudgeRegistry(arg ? new anchor.web3.PublicKey(arg) : signer)
+      .accounts({
+        admin: signer,
+      })
+      .rpc();
+  } else if (command === "set-authority") {
+    await program.methods
+      .setJudgeRegistryAuthority(requireArg())
+      .accounts({
+        authority: signer,
+      })
+      .rpc();
+  } else if (command === "add") {
+    await program.methods
+      .addJudge(requireArg())
+      .accounts({
+        authority: signer,
+        payer: signer,
+      })
+      .rpc();
+  } else if (command === "suspend" || command === "resume") {
+    await program.methods
+      .setJudgeActive(command === "resume")
+      .accounts({
+        judgeEntry: getJudgeEntryPda(program.programId, requireArg()),
+        authority: signer,
+      })
+      .rpc();
+  } else if (command === "remove") {
+    await program.methods
+      .removeJudge()
+      .accounts({
+        judgeEntry: getJudgeEntryPda(program.programId, requireArg()),
+        authority: signer,
+      })
+      .rpc();
+  } else if (command !== "show") {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const registry = await program.account.judgeRegistry.fetch(registryPda);
+  console.log("judge_registry:", registryPda.toBase58());
+  console.log("authority:", registry.authority.toBase58());
+  if (arg && command !== "init" && command !== "set-authority" && command !== "remove") {
+    const entryPda = getJudgeEntryPda(program.programId, new anchor.web3.PublicKey(arg));
+    const entry = await program.account.judgeEntry.fetch(entryPda);
+    console.log("judge_entry:", entryPda.toBase58());
+    console.log("judge:", entry.judge.toBase58());
+    console.log("active:", entry.active);
+  }
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,95 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import {
+  executeProposalInstructions,
+  getProposalInstructionPda,
+  getTreasuryVaultPda,
+  insertProposalInstruction,
+} from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "proposal_instructions.ts",
+  "<add-transfer <PROPOSAL_PDA> <LAMPORTS> <RECIPIENT> | add <PROPOSAL_PDA> <PROGRAM_ID> <DATA_HEX> [PUBKEY[:FLAGS] ...] | execute <PROPOSAL_PDA> [INDEX] | show <PROPOSAL_PDA>>\n  FLAGS: w = writable, s = signer (only the treasury vault may sign)"
+);
+
+function parseMeta(arg: string): anchor.web3.AccountMeta {
+  const [pubkey, flags = ""] = arg.split(":");
+  return {
+    pubkey: new anchor.web3.PublicKey(pubkey),
+    isSigner: flags.includes("s"),
+    isWritable: flags.includes("w"),
+  };
+}
+
+async function main() {
+  const [command, proposalArg, ...rest] = process.argv.slice(2);
+  if (!command || !proposalArg) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const signer = provider.wallet.publicKey;
+  const proposalPda = new anchor.web3.PublicKey(proposalArg);
+
+  if (command === "add-transfer") {
+    const [lamports, recipient] = rest;
+    if (!lamports || !recipient) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const ix = anchor.web3.SystemProgram.transfer({
+      fromPubkey: getTreasuryVaultPda(program.programId),
+      toPubkey: new anchor.web3.PublicKey(recipient),
+      lamports: Number(lamports),
+    });
+    const index = await insertProposalInstruction(program as any, proposalPda, signer, ix);
+    console.log("inserted_index:", index);
+  } else if (command === "add") {
+    const [programId, dataHex, ...metas] = rest;
+    if (!programId || dataHex === undefined) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    const ix = new anchor.web3.TransactionInstruction({
+      programId: new anchor.web3.PublicKey(programId),
+      keys: metas.map(parseMeta),
+      data: Buffer.from(dataHex, "hex"),
+    });
+    const index = await insertProposalInstruction(program as any, proposalPda, signer, ix);
+    console.log("inserted_index:", index);
+  } else if (command === "execute") {
+    const index = rest[0] === undefined ? undefined : Number(rest[0]);
+    const executed = await executeProposalInstructions(
+      program as any,
+      proposalPda,
+      signer,
+      index
+    );
+    console.log("executed_instructions:", executed);
+  } else if (command !== "show") {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const proposal = await program.account.proposal.fetch(proposalPda);
+  console.log("proposal:", proposalPda.toBase58());
+  console.log("final_verdict:", proposal.finalVerdict);
+  console.log("instruction_count:", proposal.instructionCount);
+  for (let i = 0; i < proposal.instructionCount; i++) {
+    const instructionPda = getProposalInstructionPda(program.programId, proposalPda, i);
+    const instruction = await program.account.proposalInstruction.fetch(instructionPda);
+    console.log(`instruction_${i}:`, instructionPda.toBase58());
+    console.log(`instruction_${i}_program:`, instruction.programId.toBase58());
+    console.log(`instruction_${i}_accounts:`, instruction.accounts.length);
+    console.log(`instruction_${i}_data:`, Buffer.from(instruction.data).toString("hex"));
+    console.log(`instruction_${i}_executed:`, instruction.executed);
+  }
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,38 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { getJudgeResultPda } from "./governance";
+import { usage } from "./utils";
+
+async function main() {
+  const [proposalArg, judgeArg] = process.argv.slice(2);
+  if (!proposalArg || !judgeArg) {
+    console.error(usage("read_judge_result.ts", "<PROPOSAL_PDA> <JUDGE_PUBKEY>"));
+    process.exit(1);
+  }
+
+  const { program } = getProgram();
+
+  const resultPda = getJudgeResultPda(
+    program.programId,
+    new anchor.web3.PublicKey(proposalArg),
+    new anchor.web3.PublicKey(judgeArg)
+  );
+  const result = await program.account.judgeResult.fetch(resultPda);
+
+  console.log("judge_result:", resultPda.toBase58());
+  console.log("proposal:", result.proposal.toBase58());
+  console.log("judge:", result.judge.toBase58());
+  console.log("revealed:", result.revealed);
+  console.log("verdict:", result.verdict);
+  console.log("commitment:", Buffer.from(result.commitment).toString("hex"));
+  console.log("prompt_hash:", Buffer.from(result.promptHash).toString("hex"));
+  console.log("model_id:", result.modelId);
+  console.log("rationale_hash:", Buffer.from(result.rationaleHash).toString("hex"));
+  console.log("receipt_root:", Buffer.from(result.receiptRoot).toString("hex"));
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
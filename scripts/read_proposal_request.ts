@@ -21,6 +21,11 @@ async function main() {
   console.log("receipt_root:", Buffer.from(req.receiptRoot).toString("hex"));
   console.log("prompt_hash:", Buffer.from(req.promptHash).toString("hex"));
   console.log("model_id:", req.modelId);
+  console.log("relayer:", req.relayer.toBase58());
+  console.log("created_at:", req.createdAt.toString());
+  console.log("expires_at:", req.expiresAt.toString());
+  console.log("fee_lamports:", req.feeLamports.toString());
+  console.log("challenge_ends_at:", req.challengeEndsAt.toString());
   console.log("source:", req.source);
   console.log("proposal_id:", req.proposalId);
   console.log("proposal_text:", req.proposalText);
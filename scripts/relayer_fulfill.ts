@@ -2,6 +2,12 @@ import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { callAmbient } from "./ambient";
 import { getProgram } from "./anchor";
+import {
+    attestationInstructions,
+    attestationMessage,
+    deliverCallback,
+    resolveRelayerEntry,
+} from "./config";
 import {
     extractJsonBlock,
     getArgOrExit,
@@ -92,21 +98,57 @@ async function main() {
     const decision = parseDecision(responseText);
     console.log("parsed decision:", decision);
     const responseHash = sha256Bytes(responseText);
+    const promptHash = sha256Bytes(prompt);
+    const message = attestationMessage(
+        requestPda,
+        decision,
+        responseHash,
+        receiptRootBytes,
+        promptHash,
+        MODEL_ID
+    );
 
-    const sig = await program.methods
-        .fulfillJudgeRequest(decision, responseHash as any, receiptRootBytes as any)
+    const quorumMode = req.relayerQuorum > 1;
+    const args = [
+        decision,
+        responseHash as any,
+        receiptRootBytes as any,
+        promptHash as any,
+        MODEL_ID,
+    ] as const;
+    const method = quorumMode
+        ? program.methods.submitRelayerResult(...args)
+        : program.methods.fulfillJudgeRequest(...args);
+    const sig = await method
         .accounts({
             request: requestPda,
+            relayerEntry: await resolveRelayerEntry(program as any, user),
             relayer: user,
-        })
+        } as any)
+        .preInstructions(attestationInstructions(message))
         .rpc();
 
     console.log("fulfilled tx:", sig);
 
     const updated = await program.account.judgeRequest.fetch(requestPda);
     console.log("updated status:", updated.status);
+    if (quorumMode) {
+        console.log(
+            "relayer submissions:",
+            `${updated.submissions}/${updated.relayerPanel} (quorum ${updated.relayerQuorum})`
+        );
+    }
     console.log("stored decision:", updated.decision);
+    console.log("fulfilled by:", updated.relayer.toBase58());
     console.log("stored response_hash (first 8 bytes):", Buffer.from(updated.responseHash).toString("hex").slice(0, 16));
+
+    if (updated.status === 1 && !updated.callbackProgram.equals(anchor.web3.PublicKey.default)) {
+        try {
+            console.log("callback tx:", await deliverCallback(program as any, requestPda));
+        } catch (e) {
+            console.error("callback delivery failed (retry with deliver_callback.ts):", e);
+        }
+    }
 }
 
 main().catch((e) => {
@@ -2,6 +2,7 @@ import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { AmbientApiError, callAmbient } from "./ambient";
 import { getProgram } from "./anchor";
+import { attestationInstructions, attestationMessage, resolveRelayerEntry } from "./config";
 import {
   fetchVotesSummary,
   GovernanceSource,
@@ -137,13 +138,38 @@ async function main() {
     )
     .accounts({
       request: requestPda,
+      relayerEntry: await resolveRelayerEntry(program as any, user),
       relayer: user,
-    })
+    } as any)
+    .preInstructions(
+      attestationInstructions(
+        attestationMessage(
+          requestPda,
+          verdictCode,
+          summaryHash,
+          receiptRootBytes,
+          promptHash,
+          MODEL_ID
+        )
+      )
+    )
     .rpc();
 
   console.log("fulfilled tx:", sig);
 
-  const updated = await program.account.proposalRequest.fetch(requestPda);
+  let updated = await program.account.proposalRequest.fetch(requestPda);
+  if (Number(updated.challengeEndsAt.toString()) <= Math.floor(Date.now() / 1000)) {
+    await program.methods
+      .finalizeProposalRequest()
+      .accounts({
+        request: requestPda,
+        relayer: user,
+      })
+      .rpc();
+    updated = await program.account.proposalRequest.fetch(requestPda);
+  } else {
+    console.log("challenge_ends_at:", updated.challengeEndsAt.toString());
+  }
   console.log("updated status:", updated.status);
   console.log(
     "stored summary_hash (first 8 bytes):",
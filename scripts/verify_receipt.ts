@@ -0,0 +1,38 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { usage } from "./utils";
+
+function hexBytes(hex: string): number[] {
+  const bytes = Buffer.from(hex.replace(/^0x/, ""), "hex");
+  if (bytes.length !== 32) {
+    throw new Error(`expected 32-byte hex, got ${hex}`);
+  }
+  return Array.from(bytes);
+}
+
+async function main() {
+  const [accountArg, leafArg, indexArg, ...siblings] = process.argv.slice(2);
+  if (!accountArg || !leafArg || !indexArg) {
+    console.error(
+      usage("verify_receipt.ts", "<REQUEST_OR_JUDGE_RESULT_PDA> <LEAF_HEX> <LEAF_INDEX> [SIBLING_HEX...]")
+    );
+    process.exit(1);
+  }
+
+  const { program } = getProgram();
+
+  const sig = await program.methods
+    .verifyReceiptLeaf(hexBytes(leafArg), Number(indexArg), siblings.map(hexBytes))
+    .accounts({
+      receiptAccount: new anchor.web3.PublicKey(accountArg),
+    })
+    .rpc();
+
+  console.log("receipt leaf verified:", sig);
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,113 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { randomBytes } from "crypto";
+import { getVoteRecordPda, resolveVoterDeposit, voteCommitment } from "./governance";
+import { usage } from "./utils";
+
+const USAGE = usage(
+  "vote.ts",
+  "<cast <PROPOSAL_PDA> <CHOICE> | commit <PROPOSAL_PDA> <CHOICE> | reveal <PROPOSAL_PDA> <CHOICE> <SALT_HEX> | change <PROPOSAL_PDA> <CHOICE> | withdraw <PROPOSAL_PDA>>"
+);
+
+async function main() {
+  const [command, proposalArg, choiceArg, saltArg] = process.argv.slice(2);
+  if (!command || !proposalArg) {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const { provider, program } = getProgram();
+  const voter = provider.wallet.publicKey;
+  const proposalPda = new anchor.web3.PublicKey(proposalArg);
+  const recordPda = getVoteRecordPda(program.programId, proposalPda, voter);
+
+  const requireChoice = (): number => {
+    if (!choiceArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    return Number(choiceArg);
+  };
+
+  const delegatorRecords = async (): Promise<anchor.web3.AccountMeta[]> => {
+    const record = await program.account.voteRecord.fetch(recordPda);
+    return record.delegators.map((delegator: anchor.web3.PublicKey) => ({
+      pubkey: getVoteRecordPda(program.programId, proposalPda, delegator),
+      isSigner: false,
+      isWritable: true,
+    }));
+  };
+
+  if (command === "cast") {
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    await program.methods
+      .castVote(requireChoice())
+      .accounts({
+        proposal: proposalPda,
+        voterDeposit: resolveVoterDeposit(program.programId, proposal, voter),
+        voter,
+      } as any)
+      .rpc();
+  } else if (command === "commit") {
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    const salt = randomBytes(32);
+    await program.methods
+      .commitVote(voteCommitment(requireChoice(), salt, voter))
+      .accounts({
+        proposal: proposalPda,
+        voterDeposit: resolveVoterDeposit(program.programId, proposal, voter),
+        voter,
+      } as any)
+      .rpc();
+    console.log("salt (keep this to reveal):", salt.toString("hex"));
+  } else if (command === "reveal") {
+    if (!saltArg) {
+      console.error(USAGE);
+      process.exit(1);
+    }
+    await program.methods
+      .revealVote(requireChoice(), Array.from(Buffer.from(saltArg, "hex")))
+      .accounts({
+        proposal: proposalPda,
+        voter,
+      })
+      .remainingAccounts(await delegatorRecords())
+      .rpc();
+  } else if (command === "change") {
+    await program.methods
+      .changeVote(requireChoice())
+      .accounts({
+        proposal: proposalPda,
+        voter,
+      })
+      .remainingAccounts(await delegatorRecords())
+      .rpc();
+  } else if (command === "withdraw") {
+    await program.methods
+      .withdrawVote()
+      .accounts({
+        proposal: proposalPda,
+        voter,
+      })
+      .remainingAccounts(await delegatorRecords())
+      .rpc();
+  } else {
+    console.error(USAGE);
+    process.exit(1);
+  }
+
+  const proposal = await program.account.proposal.fetch(proposalPda);
+  console.log("proposal:", proposalPda.toBase58());
+  console.log("votes_for:", proposal.votesFor.toString());
+  console.log("votes_against:", proposal.votesAgainst.toString());
+  console.log("votes_abstain:", proposal.votesAbstain.toString());
+  if (proposal.secretBallot) {
+    console.log("unrevealed_votes:", proposal.unrevealedVotes.toString());
+  }
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -0,0 +1,60 @@
+import "dotenv/config";
+import * as anchor from "@coral-xyz/anchor";
+import { getProgram } from "./anchor";
+import { usage } from "./utils";
+
+const USAGE = usage("watch_events.ts", "[EVENT_NAME ...]");
+
+function format(value: any): any {
+  if (value instanceof anchor.web3.PublicKey) return value.toBase58();
+  if (anchor.BN.isBN(value)) return value.toString();
+  if (Array.isArray(value) && value.length === 32) {
+    return Buffer.from(value).toString("hex");
+  }
+  return value;
+}
+
+async function main() {
+  const names = process.argv.slice(2);
+  if (names.includes("--help")) {
+    console.log(USAGE);
+    return;
+  }
+
+  const { program } = getProgram();
+  const known = (program.idl.events ?? []).map((event) => event.name);
+  const unknown = names.filter((name) => !known.includes(name));
+  if (unknown.length > 0) {
+    console.error("unknown events:", unknown.join(", "));
+    console.error("known events:", known.join(", "));
+    process.exit(1);
+  }
+
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  program.provider.connection.onLogs(
+    program.programId,
+    (logs) => {
+      if (logs.err) return;
+      for (const event of parser.parseLogs(logs.logs)) {
+        if (names.length > 0 && !names.includes(event.name)) continue;
+        const data = Object.fromEntries(
+          Object.entries(event.data).map(([key, value]) => [key, format(value)])
+        );
+        console.log(
+          JSON.stringify({ signature: logs.signature, event: event.name, data })
+        );
+      }
+    },
+    "confirmed"
+  );
+  console.log(
+    "watching",
+    program.programId.toBase58(),
+    names.length > 0 ? names.join(", ") : "(all events)"
+  );
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -9,6 +9,17 @@ describe("ambient_svm_hello (devnet)", () => {
 
   const program = anchor.workspace.AmbientSvmHello as Program<AmbientSvmHello>;
 
+  const singleRelayer = { relayerQuorum: 1, relayerPanel: 1, matchResponseHash: false };
+
+  const minRequestFee = async (): Promise<anchor.BN> => {
+    const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+    const cfg = await program.account.config.fetch(configPda);
+    return cfg.minRequestFee;
+  };
+
   it("init_config (if needed) + create_judge_request", async () => {
     const user = provider.wallet.publicKey;
 
@@ -58,7 +69,7 @@ Add .env.example with placeholder value and README instructions to copy it to .e
     );
 
     await program.methods
-      .createJudgeRequest(criteria, inputA, inputB, nonce)
+      .createJudgeRequest(criteria, inputA, inputB, nonce, await minRequestFee(), singleRelayer, null)
       .accounts({
         user,
       })
@@ -110,7 +121,7 @@ Add .env.example with placeholder value and README instructions to copy it to .e
     );
 
     await program.methods
-      .createProposalRequest(source, proposalId, proposalText, nonce)
+      .createProposalRequest(source, proposalId, proposalText, nonce, await minRequestFee())
       .accounts({
         user,
       })
@@ -121,4 +132,47 @@ Add .env.example with placeholder value and README instructions to copy it to .e
     const req = await program.account.proposalRequest.fetch(requestPda);
     console.log("proposal status:", req.status);
   });
+
+  it("cancel_judge_request closes a pending request", async () => {
+    const user = provider.wallet.publicKey;
+    const nonce = new anchor.BN(Date.now());
+
+    const [requestPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("req"), user.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createJudgeRequest(
+        "Pick the shorter input.",
+        "a",
+        "bb",
+        nonce,
+        await minRequestFee(),
+        singleRelayer,
+        null
+      )
+      .accounts({
+        user,
+      })
+      .rpc();
+
+    const req = await program.account.judgeRequest.fetch(requestPda);
+    console.log("created_at:", req.createdAt.toString());
+    console.log("expires_at:", req.expiresAt.toString());
+
+    await program.methods
+      .cancelJudgeRequest()
+      .accounts({
+        request: requestPda,
+        authority: user,
+      })
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(requestPda);
+    if (closed) {
+      throw new Error("request account still open after cancel");
+    }
+    console.log("request cancelled:", requestPda.toBase58());
+  });
 });